@@ -23,6 +23,32 @@ pub enum AttrId {
     ShareIdentifier,
     /// codec-specific threshold signature data
     ThresholdData,
+    /// signing context string
+    Context,
+    /// multi-party signature public nonce
+    PublicNonce,
+    /// aggregate signature signer count
+    SignerCount,
+    /// aggregate signature signer key identifiers
+    SignerKeyIds,
+    /// aggregate signature messages
+    Messages,
+    /// recoverable signature recovery id
+    RecoveryId,
+    /// recoverable signature EIP-155 chain id
+    ChainId,
+    /// the encoding the signature data arrived in
+    SigEncoding,
+    /// stateful hash-based signature one-time leaf index
+    LeafIndex,
+    /// stateful hash-based signature tree height
+    TreeHeight,
+    /// selective disclosure proof disclosed message indexes
+    DisclosedIndexes,
+    /// selective disclosure proof presentation header
+    PresentationHeader,
+    /// RSA modulus size in bytes
+    ModulusSize,
 }
 
 impl AttrId {
@@ -41,6 +67,19 @@ impl AttrId {
             Self::Limit => "limit",
             Self::ShareIdentifier => "share-identifier",
             Self::ThresholdData => "threshold-data",
+            Self::Context => "context",
+            Self::PublicNonce => "public-nonce",
+            Self::SignerCount => "signer-count",
+            Self::SignerKeyIds => "signer-key-ids",
+            Self::Messages => "messages",
+            Self::RecoveryId => "recovery-id",
+            Self::ChainId => "chain-id",
+            Self::SigEncoding => "sig-encoding",
+            Self::LeafIndex => "leaf-index",
+            Self::TreeHeight => "tree-height",
+            Self::DisclosedIndexes => "disclosed-indexes",
+            Self::PresentationHeader => "presentation-header",
+            Self::ModulusSize => "modulus-size",
         }
     }
 }
@@ -63,6 +102,19 @@ impl TryFrom<u8> for AttrId {
             4 => Ok(Self::Limit),
             5 => Ok(Self::ShareIdentifier),
             6 => Ok(Self::ThresholdData),
+            7 => Ok(Self::Context),
+            8 => Ok(Self::PublicNonce),
+            9 => Ok(Self::SignerCount),
+            10 => Ok(Self::SignerKeyIds),
+            11 => Ok(Self::Messages),
+            12 => Ok(Self::RecoveryId),
+            13 => Ok(Self::ChainId),
+            14 => Ok(Self::SigEncoding),
+            15 => Ok(Self::LeafIndex),
+            16 => Ok(Self::TreeHeight),
+            17 => Ok(Self::DisclosedIndexes),
+            18 => Ok(Self::PresentationHeader),
+            19 => Ok(Self::ModulusSize),
             _ => Err(AttributesError::InvalidAttributeValue(c).into()),
         }
     }
@@ -104,6 +156,19 @@ impl TryFrom<&str> for AttrId {
             "limit" => Ok(Self::Limit),
             "share-identifier" => Ok(Self::ShareIdentifier),
             "threshold-data" => Ok(Self::ThresholdData),
+            "context" => Ok(Self::Context),
+            "public-nonce" => Ok(Self::PublicNonce),
+            "signer-count" => Ok(Self::SignerCount),
+            "signer-key-ids" => Ok(Self::SignerKeyIds),
+            "messages" => Ok(Self::Messages),
+            "recovery-id" => Ok(Self::RecoveryId),
+            "chain-id" => Ok(Self::ChainId),
+            "sig-encoding" => Ok(Self::SigEncoding),
+            "leaf-index" => Ok(Self::LeafIndex),
+            "tree-height" => Ok(Self::TreeHeight),
+            "disclosed-indexes" => Ok(Self::DisclosedIndexes),
+            "presentation-header" => Ok(Self::PresentationHeader),
+            "modulus-size" => Ok(Self::ModulusSize),
             _ => Err(AttributesError::InvalidAttributeName(s.to_string()).into()),
         }
     }
@@ -14,6 +14,9 @@ pub enum Error {
     /// Conversions error
     #[error(transparent)]
     Conversions(#[from] ConversionsError),
+    /// Aggregate error
+    #[error(transparent)]
+    Aggregate(#[from] AggregateError),
 
     /// A multibase conversion error
     #[error(transparent)]
@@ -49,6 +52,9 @@ pub enum Error {
     /// Unsupported signature algorithm
     #[error("Unsupported signature codec: {0}")]
     UnsupportedAlgorithm(String),
+    /// The view is not supported by the signature
+    #[error("Unsupported view: {0}")]
+    UnsupportedView(String),
 }
 
 /// Attributes errors created by this library
@@ -79,12 +85,84 @@ pub enum AttributesError {
     /// No threshold data attribute
     #[error("Signature missing threshold data")]
     MissingThresholdData,
+    /// No context attribute
+    #[error("Signature missing context")]
+    MissingContext,
+    /// No public nonce attribute
+    #[error("Signature missing public nonce")]
+    MissingPublicNonce,
+    /// No signer count attribute
+    #[error("Signature missing signer count")]
+    MissingSignerCount,
+    /// No signer key identifiers attribute
+    #[error("Signature missing signer key identifiers")]
+    MissingSignerKeyIds,
+    /// No messages attribute
+    #[error("Signature missing messages")]
+    MissingMessages,
+    /// No recovery id attribute
+    #[error("Signature missing recovery id")]
+    MissingRecoveryId,
+    /// No chain id attribute
+    #[error("Signature missing chain id")]
+    MissingChainId,
+    /// No leaf index attribute
+    #[error("Signature missing leaf index")]
+    MissingLeafIndex,
+    /// No tree height attribute
+    #[error("Signature missing tree height")]
+    MissingTreeHeight,
+    /// No disclosed indexes attribute
+    #[error("Proof missing disclosed indexes")]
+    MissingDisclosedIndexes,
+    /// No modulus size attribute
+    #[error("Signature missing modulus size")]
+    MissingModulusSize,
     /// Invalid attribute name
     #[error("Invalid attribute name {0}")]
     InvalidAttributeName(String),
     /// Invalid attribute value
     #[error("Invalid attribute value {0}")]
     InvalidAttributeValue(u8),
+    /// Invalid signing scheme id
+    #[error("Invalid signing scheme {0}")]
+    InvalidScheme(u8),
+    /// Invalid signing scheme name
+    #[error("Invalid signing scheme name {0}")]
+    InvalidSchemeName(String),
+    /// Invalid signature data length
+    #[error("Invalid signature length {0}")]
+    InvalidSignatureLength(usize),
+    /// Invalid signature data
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    /// Invalid context string length
+    #[error("Invalid context length {0}")]
+    InvalidContextLength(usize),
+    /// Invalid public nonce length
+    #[error("Invalid public nonce length {0}")]
+    InvalidPublicNonceLength(usize),
+    /// Invalid recovery id or v value
+    #[error("Invalid recovery id {0}")]
+    InvalidRecoveryId(u64),
+    /// Malleable high-S ECDSA signature
+    #[error("Signature has a high S value")]
+    HighS,
+    /// Public key recovery failed
+    #[error("Public key recovery failed: {0}")]
+    RecoveryFailed(String),
+    /// Leaf index outside of the tree or not matching the signature
+    #[error("Invalid leaf index {0}")]
+    InvalidLeafIndex(u64),
+    /// Unsupported tree height
+    #[error("Invalid tree height {0}")]
+    InvalidTreeHeight(u8),
+    /// Invalid disclosed indexes
+    #[error("Invalid disclosed indexes: {0}")]
+    InvalidDisclosedIndexes(String),
+    /// Unsupported RSA modulus size
+    #[error("Invalid modulus size {0}")]
+    InvalidModulusSize(usize),
 }
 
 /// Shares errors created by this library
@@ -121,6 +199,51 @@ pub enum SharesError {
     /// Not enough shares to reconstruct the siganture
     #[error("Not enough shares to reconstruct the signature")]
     NotEnoughShares,
+    /// Invalid share identifier
+    #[error("Invalid share identifier {0}")]
+    InvalidShareIdentifier(u8),
+    /// Signing package mismatch
+    #[error("Signature share signing package mismatch")]
+    SigningPackageMismatch,
+    /// A signature share is inconsistent, identifying the signer to blame
+    #[error("Signature share {0} is inconsistent with the presignature")]
+    IdentifiableAbort(u8),
+}
+
+/// Aggregate errors created by this library
+#[derive(Clone, Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum AggregateError {
+    /// Signature codec mismatch
+    #[error("Aggregate signature codec mismatch: {0}")]
+    CodecMismatch(multicodec::Codec),
+    /// Signature scheme does not protect against rogue key attacks
+    #[error("Aggregate signatures require the proof-of-possession scheme")]
+    ProofOfPossessionRequired,
+    /// Signature message mismatch
+    #[error("Aggregate signature message mismatch")]
+    MessageMismatch,
+    /// Signature is missing the message it signs
+    #[error("Aggregated signature missing message")]
+    MissingMessage,
+    /// Duplicate message
+    #[error("Duplicate aggregate signature message")]
+    DuplicateMessage,
+    /// Signature scheme mismatch
+    #[error("Aggregate signature scheme mismatch")]
+    SchemeMismatch,
+    /// Same-message and distinct-message aggregation mixed
+    #[error("Cannot mix same-message and distinct-message signature aggregation")]
+    MixedAggregate,
+    /// Duplicate signer key identifier
+    #[error("Duplicate aggregate signature signer")]
+    DuplicateSigner,
+    /// Signer count does not match the signer key identifiers
+    #[error("Aggregate signature signer count mismatch")]
+    SignerCountMismatch,
+    /// Aggregating the signatures failed
+    #[error("Signature aggregation failed: {0}")]
+    AggregateFailed(String),
 }
 
 /// Conversion errors
@@ -130,6 +253,9 @@ pub enum ConversionsError {
     /// Ssh conversion error
     #[error(transparent)]
     Ssh(#[from] SshError),
+    /// DER conversion error
+    #[error("Invalid DER signature: {0}")]
+    Der(String),
 }
 
 /// SSH Errors
@@ -139,6 +265,8 @@ pub enum SshError {
     Sig(ssh_key::Error),
     /// SSH Sig label
     SigLabel(ssh_encoding::LabelError),
+    /// SSH Sig encoding
+    SigEncoding(ssh_encoding::Error),
 }
 
 impl Display for SshError {
@@ -146,6 +274,7 @@ impl Display for SshError {
         match self {
             SshError::Sig(e) => write!(f, "SSH Sig error: {}", e),
             SshError::SigLabel(e) => write!(f, "SSH Sig label error: {}", e),
+            SshError::SigEncoding(e) => write!(f, "SSH Sig encoding error: {}", e),
         }
     }
 }
@@ -163,3 +292,9 @@ impl From<ssh_encoding::LabelError> for SshError {
         SshError::SigLabel(e)
     }
 }
+
+impl From<ssh_encoding::Error> for SshError {
+    fn from(e: ssh_encoding::Error) -> Self {
+        SshError::SigEncoding(e)
+    }
+}
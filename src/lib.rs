@@ -22,7 +22,10 @@ pub use ms::{SIG_CODECS, SIG_SHARE_CODECS, Builder, EncodedMultisig, Multisig};
 
 /// Views on the multisig
 pub mod views;
-pub use views::{AttrView, ConvView, DataView, ThresholdAttrView, ThresholdView, Views};
+pub use views::{
+    AggregateView, AttrView, BbsView, ConvView, DataView, EcdsaView, RecoveryView, StatefulView,
+    ThresholdAttrView, ThresholdView, Views,
+};
 
 /// Serde serialization
 #[cfg(feature = "serde")]
@@ -2,10 +2,13 @@
 use crate::{
     error::AttributesError,
     views::{
+        aggregate, bbs,
         bls12381::{self, SchemeTypeId},
-        ed25519, secp256k1,
+        ecdsa, ed25519, ed25519_halfagg, ed448, eddsa, frost, lamport, musig2, p256, p384, p521,
+        rsa, secp256k1, sr25519, threshold_ecdsa, xeddsa, xmss,
     },
-    AttrId, AttrView, ConvView, DataView, Error, ThresholdAttrView, ThresholdView, Views,
+    AggregateView, AttrId, AttrView, BbsView, ConvView, DataView, EcdsaView, Error, RecoveryView,
+    StatefulView, ThresholdAttrView, ThresholdView, Views,
 };
 use blsful::{inner_types::GroupEncoding, vsss_rs::Share, Signature, SignatureShare};
 use multibase::Base;
@@ -15,29 +18,30 @@ use multiutil::{BaseEncoded, CodecInfo, EncodingInfo, Varbytes, Varuint};
 use std::{collections::BTreeMap, fmt};
 
 /// the list of signature codecs currently supported
-pub const SIG_CODECS: [Codec; 4] = [
+pub const SIG_CODECS: [Codec; 9] = [
     Codec::Bls12381G1Msig,
     Codec::Bls12381G2Msig,
     Codec::EddsaMsig,
-    // Codec::Es256Msig,
-    // Codec::Es384Msig,
-    // Codec::Es521Msig,
-    // Codec::Rs256Msig,
-    Codec::Es256KMsig, //,
-                       //Codec::LamportMsig,
+    Codec::Es256Msig,
+    Codec::Es384Msig,
+    Codec::Es521Msig,
+    Codec::Rs256Msig,
+    Codec::Es256KMsig,
+    Codec::LamportMsig,
 ];
 
 /// the list of signature share codecs supported
-pub const SIG_SHARE_CODECS: [Codec; 2] = [
+pub const SIG_SHARE_CODECS: [Codec; 3] = [
     Codec::Bls12381G1ShareMsig,
-    Codec::Bls12381G2ShareMsig, //,
-                                //Codec::LamportShareMsig,
+    Codec::Bls12381G2ShareMsig,
+    Codec::LamportShareMsig,
 ];
 
 /// the multisig sigil
 pub const SIGIL: Codec = Codec::Multisig;
 
-/// a base encoded varsig
+/// a base encoded varsig. decoding does not reject malleable high-S ECDSA
+/// signatures, use [`Multisig::try_decode_strict`] for that.
 pub type EncodedMultisig = BaseEncoded<Multisig>;
 
 /// The multisig attributes type
@@ -97,6 +101,8 @@ impl From<Multisig> for Vec<u8> {
     }
 }
 
+/// decoding does not reject malleable high-S ECDSA signatures, use
+/// [`Multisig::try_decode_strict`] for that
 impl<'a> TryFrom<&'a [u8]> for Multisig {
     type Error = Error;
 
@@ -150,6 +156,17 @@ impl<'a> TryDecodeFrom<'a> for Multisig {
     }
 }
 
+impl Multisig {
+    /// decode a Multisig in strict mode, which also rejects malleable high-S
+    /// ECDSA signatures. this is the only decoding path that does so,
+    /// `TryFrom<&[u8]>`, [`EncodedMultisig`] and serde accept them.
+    pub fn try_decode_strict(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (ms, ptr) = Self::try_decode_from(bytes)?;
+        ecdsa::check_low_s(&ms)?;
+        Ok((ms, ptr))
+    }
+}
+
 impl Null for Multisig {
     fn null() -> Self {
         Self::default()
@@ -180,55 +197,258 @@ impl Views for Multisig {
     /// Provide a read-only view to access the signature attributes
     fn attr_view<'a>(&'a self) -> Result<Box<dyn AttrView + 'a>, Error> {
         match self.codec {
+            Codec::Bls12381G1Msig if bbs::is_bbs(self) => Ok(Box::new(bbs::View::try_from(self)?)),
             Codec::Bls12381G1Msig
             | Codec::Bls12381G2Msig
             | Codec::Bls12381G1ShareMsig
             | Codec::Bls12381G2ShareMsig => Ok(Box::new(bls12381::View::try_from(self)?)),
-            Codec::EddsaMsig => Ok(Box::new(ed25519::View::try_from(self)?)),
-            Codec::Es256KMsig => Ok(Box::new(secp256k1::View::try_from(self)?)),
+            Codec::EddsaMsig => match eddsa::SchemeTypeId::try_from(self)? {
+                eddsa::SchemeTypeId::Ed448 => Ok(Box::new(ed448::View::try_from(self)?)),
+                eddsa::SchemeTypeId::FrostShare => Ok(Box::new(frost::View::try_from(self)?)),
+                eddsa::SchemeTypeId::Ed25519HalfAggregate => {
+                    Ok(Box::new(ed25519_halfagg::View::try_from(self)?))
+                }
+                eddsa::SchemeTypeId::XEdDsa => Ok(Box::new(xeddsa::View::try_from(self)?)),
+                eddsa::SchemeTypeId::Sr25519 => Ok(Box::new(sr25519::View::try_from(self)?)),
+                _ => Ok(Box::new(ed25519::View::try_from(self)?)),
+            },
+            Codec::Es256Msig => match p256::SchemeTypeId::try_from(self)? {
+                p256::SchemeTypeId::EcdsaShare => {
+                    Ok(Box::new(threshold_ecdsa::View::try_from(self)?))
+                }
+                _ => Ok(Box::new(p256::View::try_from(self)?)),
+            },
+            Codec::Es384Msig => Ok(Box::new(p384::View::try_from(self)?)),
+            Codec::Es521Msig => Ok(Box::new(p521::View::try_from(self)?)),
+            Codec::Rs256Msig => Ok(Box::new(rsa::View::try_from(self)?)),
+            Codec::Es256KMsig => match secp256k1::SchemeTypeId::try_from(self)? {
+                secp256k1::SchemeTypeId::FrostShare
+                | secp256k1::SchemeTypeId::FrostTaprootShare => {
+                    Ok(Box::new(frost::View::try_from(self)?))
+                }
+                secp256k1::SchemeTypeId::Musig2Partial => {
+                    Ok(Box::new(musig2::View::try_from(self)?))
+                }
+                secp256k1::SchemeTypeId::EcdsaShare => {
+                    Ok(Box::new(threshold_ecdsa::View::try_from(self)?))
+                }
+                _ => Ok(Box::new(secp256k1::View::try_from(self)?)),
+            },
+            Codec::LamportMsig if xmss::is_stateful(self) => {
+                Ok(Box::new(xmss::View::try_from(self)?))
+            }
+            Codec::LamportMsig | Codec::LamportShareMsig => {
+                Ok(Box::new(lamport::View::try_from(self)?))
+            }
             _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
         }
     }
     /// Provide a read-only view to access signature data
     fn data_view<'a>(&'a self) -> Result<Box<dyn DataView + 'a>, Error> {
         match self.codec {
+            Codec::Bls12381G1Msig if bbs::is_bbs(self) => Ok(Box::new(bbs::View::try_from(self)?)),
             Codec::Bls12381G1Msig
             | Codec::Bls12381G2Msig
             | Codec::Bls12381G1ShareMsig
             | Codec::Bls12381G2ShareMsig => Ok(Box::new(bls12381::View::try_from(self)?)),
-            Codec::EddsaMsig => Ok(Box::new(ed25519::View::try_from(self)?)),
-            Codec::Es256KMsig => Ok(Box::new(secp256k1::View::try_from(self)?)),
+            Codec::EddsaMsig => match eddsa::SchemeTypeId::try_from(self)? {
+                eddsa::SchemeTypeId::Ed448 => Ok(Box::new(ed448::View::try_from(self)?)),
+                eddsa::SchemeTypeId::FrostShare => Ok(Box::new(frost::View::try_from(self)?)),
+                eddsa::SchemeTypeId::Ed25519HalfAggregate => {
+                    Ok(Box::new(ed25519_halfagg::View::try_from(self)?))
+                }
+                eddsa::SchemeTypeId::XEdDsa => Ok(Box::new(xeddsa::View::try_from(self)?)),
+                eddsa::SchemeTypeId::Sr25519 => Ok(Box::new(sr25519::View::try_from(self)?)),
+                _ => Ok(Box::new(ed25519::View::try_from(self)?)),
+            },
+            Codec::Es256Msig => match p256::SchemeTypeId::try_from(self)? {
+                p256::SchemeTypeId::EcdsaShare => {
+                    Ok(Box::new(threshold_ecdsa::View::try_from(self)?))
+                }
+                _ => Ok(Box::new(p256::View::try_from(self)?)),
+            },
+            Codec::Es384Msig => Ok(Box::new(p384::View::try_from(self)?)),
+            Codec::Es521Msig => Ok(Box::new(p521::View::try_from(self)?)),
+            Codec::Rs256Msig => Ok(Box::new(rsa::View::try_from(self)?)),
+            Codec::Es256KMsig => match secp256k1::SchemeTypeId::try_from(self)? {
+                secp256k1::SchemeTypeId::FrostShare
+                | secp256k1::SchemeTypeId::FrostTaprootShare => {
+                    Ok(Box::new(frost::View::try_from(self)?))
+                }
+                secp256k1::SchemeTypeId::Musig2Partial => {
+                    Ok(Box::new(musig2::View::try_from(self)?))
+                }
+                secp256k1::SchemeTypeId::EcdsaShare => {
+                    Ok(Box::new(threshold_ecdsa::View::try_from(self)?))
+                }
+                _ => Ok(Box::new(secp256k1::View::try_from(self)?)),
+            },
+            Codec::LamportMsig if xmss::is_stateful(self) => {
+                Ok(Box::new(xmss::View::try_from(self)?))
+            }
+            Codec::LamportMsig | Codec::LamportShareMsig => {
+                Ok(Box::new(lamport::View::try_from(self)?))
+            }
             _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
         }
     }
     /// Provide a read-only view to access signature data
     fn conv_view<'a>(&'a self) -> Result<Box<dyn ConvView + 'a>, Error> {
         match self.codec {
+            Codec::Bls12381G1Msig if bbs::is_bbs(self) => Ok(Box::new(bbs::View::try_from(self)?)),
             Codec::Bls12381G1Msig
             | Codec::Bls12381G2Msig
             | Codec::Bls12381G1ShareMsig
             | Codec::Bls12381G2ShareMsig => Ok(Box::new(bls12381::View::try_from(self)?)),
-            Codec::EddsaMsig => Ok(Box::new(ed25519::View::try_from(self)?)),
-            Codec::Es256KMsig => Ok(Box::new(secp256k1::View::try_from(self)?)),
+            Codec::EddsaMsig => match eddsa::SchemeTypeId::try_from(self)? {
+                eddsa::SchemeTypeId::Ed448 => Ok(Box::new(ed448::View::try_from(self)?)),
+                eddsa::SchemeTypeId::FrostShare => Ok(Box::new(frost::View::try_from(self)?)),
+                eddsa::SchemeTypeId::Ed25519HalfAggregate => {
+                    Ok(Box::new(ed25519_halfagg::View::try_from(self)?))
+                }
+                eddsa::SchemeTypeId::XEdDsa => Ok(Box::new(xeddsa::View::try_from(self)?)),
+                eddsa::SchemeTypeId::Sr25519 => Ok(Box::new(sr25519::View::try_from(self)?)),
+                _ => Ok(Box::new(ed25519::View::try_from(self)?)),
+            },
+            Codec::Es256Msig => match p256::SchemeTypeId::try_from(self)? {
+                p256::SchemeTypeId::EcdsaShare => {
+                    Ok(Box::new(threshold_ecdsa::View::try_from(self)?))
+                }
+                _ => Ok(Box::new(p256::View::try_from(self)?)),
+            },
+            Codec::Es384Msig => Ok(Box::new(p384::View::try_from(self)?)),
+            Codec::Es521Msig => Ok(Box::new(p521::View::try_from(self)?)),
+            Codec::Rs256Msig => Ok(Box::new(rsa::View::try_from(self)?)),
+            Codec::Es256KMsig => match secp256k1::SchemeTypeId::try_from(self)? {
+                secp256k1::SchemeTypeId::FrostShare
+                | secp256k1::SchemeTypeId::FrostTaprootShare => {
+                    Ok(Box::new(frost::View::try_from(self)?))
+                }
+                secp256k1::SchemeTypeId::Musig2Partial => {
+                    Ok(Box::new(musig2::View::try_from(self)?))
+                }
+                secp256k1::SchemeTypeId::EcdsaShare => {
+                    Ok(Box::new(threshold_ecdsa::View::try_from(self)?))
+                }
+                _ => Ok(Box::new(secp256k1::View::try_from(self)?)),
+            },
+            Codec::LamportMsig if xmss::is_stateful(self) => {
+                Ok(Box::new(xmss::View::try_from(self)?))
+            }
+            Codec::LamportMsig | Codec::LamportShareMsig => {
+                Ok(Box::new(lamport::View::try_from(self)?))
+            }
             _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
         }
     }
     /// Provide a read-only view to access the threshold signature attributes
     fn threshold_attr_view<'a>(&'a self) -> Result<Box<dyn ThresholdAttrView + 'a>, Error> {
         match self.codec {
+            Codec::Bls12381G1Msig if bbs::is_bbs(self) => {
+                Err(Error::UnsupportedView("threshold".to_string()))
+            }
             Codec::Bls12381G1Msig
             | Codec::Bls12381G2Msig
             | Codec::Bls12381G1ShareMsig
             | Codec::Bls12381G2ShareMsig => Ok(Box::new(bls12381::View::try_from(self)?)),
+            Codec::EddsaMsig if sr25519::is_sr25519(self) => {
+                Err(Error::UnsupportedView("threshold".to_string()))
+            }
+            Codec::EddsaMsig => Ok(Box::new(frost::View::try_from(self)?)),
+            Codec::Es256Msig => Ok(Box::new(threshold_ecdsa::View::try_from(self)?)),
+            Codec::Es256KMsig => match secp256k1::SchemeTypeId::try_from(self)? {
+                secp256k1::SchemeTypeId::Ecdsa | secp256k1::SchemeTypeId::EcdsaShare => {
+                    Ok(Box::new(threshold_ecdsa::View::try_from(self)?))
+                }
+                secp256k1::SchemeTypeId::Musig2 | secp256k1::SchemeTypeId::Musig2Partial => {
+                    Ok(Box::new(musig2::View::try_from(self)?))
+                }
+                _ => Ok(Box::new(frost::View::try_from(self)?)),
+            },
+            Codec::LamportMsig if xmss::is_stateful(self) => {
+                Err(Error::UnsupportedView("threshold".to_string()))
+            }
+            Codec::LamportMsig | Codec::LamportShareMsig => {
+                Ok(Box::new(lamport::View::try_from(self)?))
+            }
             _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
         }
     }
     /// Provide the view for adding a share to a multisig
     fn threshold_view<'a>(&'a self) -> Result<Box<dyn ThresholdView + 'a>, Error> {
         match self.codec {
+            Codec::Bls12381G1Msig if bbs::is_bbs(self) => {
+                Err(Error::UnsupportedView("threshold".to_string()))
+            }
+            Codec::Bls12381G1Msig | Codec::Bls12381G2Msig => {
+                Ok(Box::new(bls12381::View::try_from(self)?))
+            }
+            Codec::EddsaMsig if sr25519::is_sr25519(self) => {
+                Err(Error::UnsupportedView("threshold".to_string()))
+            }
+            Codec::EddsaMsig => Ok(Box::new(frost::View::try_from(self)?)),
+            Codec::Es256Msig => Ok(Box::new(threshold_ecdsa::View::try_from(self)?)),
+            Codec::Es256KMsig => match secp256k1::SchemeTypeId::try_from(self)? {
+                secp256k1::SchemeTypeId::Ecdsa | secp256k1::SchemeTypeId::EcdsaShare => {
+                    Ok(Box::new(threshold_ecdsa::View::try_from(self)?))
+                }
+                secp256k1::SchemeTypeId::Musig2 | secp256k1::SchemeTypeId::Musig2Partial => {
+                    Ok(Box::new(musig2::View::try_from(self)?))
+                }
+                _ => Ok(Box::new(frost::View::try_from(self)?)),
+            },
+            Codec::LamportMsig if xmss::is_stateful(self) => {
+                Err(Error::UnsupportedView("threshold".to_string()))
+            }
+            Codec::LamportMsig => Ok(Box::new(lamport::View::try_from(self)?)),
+            _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
+        }
+    }
+    /// Provide the view for aggregating signatures into a multisig
+    fn aggregate_view<'a>(&'a self) -> Result<Box<dyn AggregateView + 'a>, Error> {
+        match self.codec {
+            Codec::Bls12381G1Msig if bbs::is_bbs(self) => {
+                Err(Error::UnsupportedView("aggregate".to_string()))
+            }
             Codec::Bls12381G1Msig | Codec::Bls12381G2Msig => {
                 Ok(Box::new(bls12381::View::try_from(self)?))
             }
+            Codec::EddsaMsig if sr25519::is_sr25519(self) => {
+                Err(Error::UnsupportedView("aggregate".to_string()))
+            }
+            Codec::EddsaMsig => Ok(Box::new(ed25519_halfagg::View::try_from(self)?)),
+            _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
+        }
+    }
+    /// Provide the view for recovering the signer of a recoverable signature
+    fn recovery_view<'a>(&'a self) -> Result<Box<dyn RecoveryView + 'a>, Error> {
+        match self.codec {
+            Codec::Es256KMsig => Ok(Box::new(secp256k1::View::try_from(self)?)),
+            _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
+        }
+    }
+    /// Provide the view for converting between ECDSA signature encodings
+    fn ecdsa_view<'a>(&'a self) -> Result<Box<dyn EcdsaView + 'a>, Error> {
+        match self.codec {
+            Codec::Es256Msig | Codec::Es384Msig | Codec::Es521Msig | Codec::Es256KMsig => {
+                Ok(Box::new(ecdsa::View::try_from(self)?))
+            }
+            _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
+        }
+    }
+    /// Provide the view for the leaf index of a stateful hash-based signature
+    fn stateful_view<'a>(&'a self) -> Result<Box<dyn StatefulView + 'a>, Error> {
+        match self.codec {
+            Codec::LamportMsig if xmss::is_stateful(self) => {
+                Ok(Box::new(xmss::View::try_from(self)?))
+            }
+            _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
+        }
+    }
+    /// Provide the view for BBS signatures and selective disclosure proofs
+    fn bbs_view<'a>(&'a self) -> Result<Box<dyn BbsView + 'a>, Error> {
+        match self.codec {
+            Codec::Bls12381G1Msig => Ok(Box::new(bbs::View::try_from(self)?)),
             _ => Err(AttributesError::UnsupportedCodec(self.codec).into()),
         }
     }
@@ -242,6 +462,8 @@ pub struct Builder {
     base_encoding: Option<Base>,
     attributes: Option<BTreeMap<AttrId, Vec<u8>>>,
     shares: Option<Vec<Multisig>>,
+    signatures: Option<Vec<(Vec<u8>, Multisig)>>,
+    message_signatures: Option<Vec<(Vec<u8>, Multisig)>>,
 }
 
 impl Builder {
@@ -256,7 +478,7 @@ impl Builder {
     /// create new multisig from ssh Signature
     pub fn new_from_ssh_signature(sig: &ssh_key::Signature) -> Result<Self, Error> {
         let mut attributes = BTreeMap::new();
-        use ssh_key::Algorithm::*;
+        use ssh_key::{Algorithm::*, EcdsaCurve, HashAlg};
         match sig.algorithm() {
             Ed25519 => {
                 attributes.insert(AttrId::SigData, sig.as_bytes().to_vec());
@@ -266,6 +488,36 @@ impl Builder {
                     ..Default::default()
                 })
             }
+            Ecdsa { curve } => {
+                let (codec, scalar_size) = match curve {
+                    EcdsaCurve::NistP256 => (Codec::Es256Msig, p256::SCALAR_SIZE),
+                    EcdsaCurve::NistP384 => (Codec::Es384Msig, p384::SCALAR_SIZE),
+                    EcdsaCurve::NistP521 => (Codec::Es521Msig, p521::SCALAR_SIZE),
+                };
+                let sig_bytes = ecdsa::from_ssh_sig_data(sig.as_bytes(), scalar_size)?;
+                attributes.insert(AttrId::SigData, sig_bytes);
+                Ok(Self {
+                    codec,
+                    attributes: Some(attributes),
+                    ..Default::default()
+                })
+            }
+            Rsa { hash: Some(hash) } => {
+                let scheme_type = match hash {
+                    HashAlg::Sha256 => rsa::SchemeTypeId::Pkcs1v15Sha256,
+                    HashAlg::Sha512 => rsa::SchemeTypeId::Pkcs1v15Sha512,
+                    _ => return Err(Error::UnsupportedAlgorithm(sig.algorithm().to_string())),
+                };
+                attributes.insert(AttrId::Scheme, scheme_type.into());
+                // RSA signatures are as long as the signing key's modulus
+                attributes.insert(AttrId::ModulusSize, Varuint(sig.as_bytes().len()).into());
+                attributes.insert(AttrId::SigData, sig.as_bytes().to_vec());
+                Ok(Self {
+                    codec: Codec::Rs256Msig,
+                    attributes: Some(attributes),
+                    ..Default::default()
+                })
+            }
             Other(name) => match name.as_str() {
                 secp256k1::ALGORITHM_NAME => {
                     attributes.insert(AttrId::SigData, sig.as_bytes().to_vec());
@@ -275,6 +527,27 @@ impl Builder {
                         ..Default::default()
                     })
                 }
+                secp256k1::ALGORITHM_NAME_RECOVERABLE => {
+                    Self::new_from_secp256k1_rsv(sig.as_bytes())
+                }
+                secp256k1::ALGORITHM_NAME_SCHNORR => {
+                    attributes.insert(AttrId::Scheme, secp256k1::SchemeTypeId::Schnorr.into());
+                    attributes.insert(AttrId::SigData, sig.as_bytes().to_vec());
+                    Ok(Self {
+                        codec: Codec::Es256KMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                secp256k1::ALGORITHM_NAME_FROST => {
+                    attributes.insert(AttrId::Scheme, secp256k1::SchemeTypeId::Frost.into());
+                    attributes.insert(AttrId::SigData, sig.as_bytes().to_vec());
+                    Ok(Self {
+                        codec: Codec::Es256KMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
                 bls12381::ALGORITHM_NAME_G1 => {
                     let sig_combined = bls12381::SigCombined::try_from(sig.as_bytes())?;
                     attributes.insert(AttrId::Scheme, sig_combined.0.into());
@@ -295,6 +568,28 @@ impl Builder {
                         ..Default::default()
                     })
                 }
+                bls12381::ALGORITHM_NAME_G1_AGGREGATE | bls12381::ALGORITHM_NAME_G2_AGGREGATE => {
+                    let sig_aggregate = bls12381::SigAggregate::try_from(sig.as_bytes())?;
+                    let codec = match name.as_str() {
+                        bls12381::ALGORITHM_NAME_G1_AGGREGATE => Codec::Bls12381G1Msig,
+                        _ => Codec::Bls12381G2Msig,
+                    };
+                    attributes.insert(AttrId::Scheme, sig_aggregate.0.into());
+                    attributes.insert(AttrId::SigData, sig_aggregate.1);
+                    attributes.insert(
+                        AttrId::SignerCount,
+                        Varuint(sig_aggregate.2 .0.len()).into(),
+                    );
+                    attributes.insert(AttrId::SignerKeyIds, sig_aggregate.2.into());
+                    if !sig_aggregate.3 .0.is_empty() {
+                        attributes.insert(AttrId::Messages, sig_aggregate.3.into());
+                    }
+                    Ok(Self {
+                        codec,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
                 bls12381::ALGORITHM_NAME_G1_SHARE => {
                     let sig_share = bls12381::SigShare::try_from(sig.as_bytes())?;
                     attributes.insert(AttrId::ShareIdentifier, Varuint(sig_share.0).into());
@@ -321,32 +616,278 @@ impl Builder {
                         ..Default::default()
                     })
                 }
+                ed25519::ALGORITHM_NAME_PH | ed25519::ALGORITHM_NAME_CTX => {
+                    let sig_context = eddsa::SigContext::try_from(sig.as_bytes())?;
+                    let scheme_type = match name.as_str() {
+                        ed25519::ALGORITHM_NAME_PH => eddsa::SchemeTypeId::Ed25519ph,
+                        _ => eddsa::SchemeTypeId::Ed25519ctx,
+                    };
+                    attributes.insert(AttrId::Scheme, scheme_type.into());
+                    attributes.insert(AttrId::SigData, sig_context.1);
+                    if !sig_context.0.is_empty() {
+                        attributes.insert(AttrId::Context, sig_context.0);
+                    }
+                    Ok(Self {
+                        codec: Codec::EddsaMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                ed25519_halfagg::ALGORITHM_NAME => {
+                    let sig_aggregate =
+                        ed25519_halfagg::SigHalfAggregate::try_from(sig.as_bytes())?;
+                    attributes.insert(
+                        AttrId::Scheme,
+                        eddsa::SchemeTypeId::Ed25519HalfAggregate.into(),
+                    );
+                    attributes.insert(AttrId::SigData, sig_aggregate.0);
+                    attributes.insert(
+                        AttrId::SignerCount,
+                        Varuint(sig_aggregate.1 .0.len()).into(),
+                    );
+                    attributes.insert(AttrId::SignerKeyIds, sig_aggregate.1.into());
+                    attributes.insert(AttrId::Messages, sig_aggregate.2.into());
+                    Ok(Self {
+                        codec: Codec::EddsaMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                sr25519::ALGORITHM_NAME => {
+                    let sig_context = sr25519::SigContext::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, eddsa::SchemeTypeId::Sr25519.into());
+                    attributes.insert(AttrId::SigData, sig_context.1);
+                    attributes.insert(AttrId::Context, sig_context.0);
+                    Ok(Self {
+                        codec: Codec::EddsaMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                xeddsa::ALGORITHM_NAME => {
+                    let sig_key_type = xeddsa::SigKeyType::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, eddsa::SchemeTypeId::XEdDsa.into());
+                    attributes.insert(AttrId::SigData, sig_key_type.1);
+                    Ok(Self {
+                        codec: Codec::EddsaMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                ed448::ALGORITHM_NAME => {
+                    let sig_context = ed448::SigContext::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, eddsa::SchemeTypeId::Ed448.into());
+                    attributes.insert(AttrId::SigData, sig_context.1);
+                    if !sig_context.0.is_empty() {
+                        attributes.insert(AttrId::Context, sig_context.0);
+                    }
+                    Ok(Self {
+                        codec: Codec::EddsaMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                frost::ALGORITHM_NAME_ED25519_SHARE => {
+                    let frost::SigSharePackage(sig_share, package) =
+                        frost::SigSharePackage::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, eddsa::SchemeTypeId::FrostShare.into());
+                    attributes.insert(AttrId::ShareIdentifier, Varuint(sig_share.0).into());
+                    attributes.insert(AttrId::Threshold, Varuint(sig_share.1).into());
+                    attributes.insert(AttrId::Limit, Varuint(sig_share.2).into());
+                    attributes.insert(AttrId::SigData, sig_share.3);
+                    attributes.insert(AttrId::ThresholdData, package.into());
+                    Ok(Self {
+                        codec: Codec::EddsaMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                frost::ALGORITHM_NAME_SECP256K1_SHARE => {
+                    let frost::SigSharePackage(sig_share, package) =
+                        frost::SigSharePackage::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, secp256k1::SchemeTypeId::FrostShare.into());
+                    attributes.insert(AttrId::ShareIdentifier, Varuint(sig_share.0).into());
+                    attributes.insert(AttrId::Threshold, Varuint(sig_share.1).into());
+                    attributes.insert(AttrId::Limit, Varuint(sig_share.2).into());
+                    attributes.insert(AttrId::SigData, sig_share.3);
+                    attributes.insert(AttrId::ThresholdData, package.into());
+                    Ok(Self {
+                        codec: Codec::Es256KMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                frost::ALGORITHM_NAME_SECP256K1_TR_SHARE => {
+                    let frost::SigSharePackage(sig_share, package) =
+                        frost::SigSharePackage::try_from(sig.as_bytes())?;
+                    attributes.insert(
+                        AttrId::Scheme,
+                        secp256k1::SchemeTypeId::FrostTaprootShare.into(),
+                    );
+                    attributes.insert(AttrId::ShareIdentifier, Varuint(sig_share.0).into());
+                    attributes.insert(AttrId::Threshold, Varuint(sig_share.1).into());
+                    attributes.insert(AttrId::Limit, Varuint(sig_share.2).into());
+                    attributes.insert(AttrId::SigData, sig_share.3);
+                    attributes.insert(AttrId::ThresholdData, package.into());
+                    Ok(Self {
+                        codec: Codec::Es256KMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                musig2::ALGORITHM_NAME_PARTIAL => {
+                    let musig2::PartialSigPackage(psig, keys) =
+                        musig2::PartialSigPackage::try_from(sig.as_bytes())?;
+                    attributes.insert(
+                        AttrId::Scheme,
+                        secp256k1::SchemeTypeId::Musig2Partial.into(),
+                    );
+                    attributes.insert(AttrId::ShareIdentifier, Varuint(psig.0).into());
+                    attributes.insert(AttrId::Threshold, Varuint(psig.1).into());
+                    attributes.insert(AttrId::Limit, Varuint(psig.2).into());
+                    attributes.insert(AttrId::PublicNonce, psig.3);
+                    attributes.insert(AttrId::SigData, psig.4);
+                    attributes.insert(AttrId::ThresholdData, keys.into());
+                    Ok(Self {
+                        codec: Codec::Es256KMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                threshold_ecdsa::ALGORITHM_NAME_SECP256K1_SHARE => {
+                    let threshold_ecdsa::SigSharePackage(sig_share, presig) =
+                        threshold_ecdsa::SigSharePackage::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, secp256k1::SchemeTypeId::EcdsaShare.into());
+                    attributes.insert(AttrId::ShareIdentifier, Varuint(sig_share.0).into());
+                    attributes.insert(AttrId::Threshold, Varuint(sig_share.1).into());
+                    attributes.insert(AttrId::Limit, Varuint(sig_share.2).into());
+                    attributes.insert(AttrId::SigData, sig_share.3);
+                    attributes.insert(AttrId::ThresholdData, presig.into());
+                    Ok(Self {
+                        codec: Codec::Es256KMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                threshold_ecdsa::ALGORITHM_NAME_P256_SHARE => {
+                    let threshold_ecdsa::SigSharePackage(sig_share, presig) =
+                        threshold_ecdsa::SigSharePackage::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, p256::SchemeTypeId::EcdsaShare.into());
+                    attributes.insert(AttrId::ShareIdentifier, Varuint(sig_share.0).into());
+                    attributes.insert(AttrId::Threshold, Varuint(sig_share.1).into());
+                    attributes.insert(AttrId::Limit, Varuint(sig_share.2).into());
+                    attributes.insert(AttrId::SigData, sig_share.3);
+                    attributes.insert(AttrId::ThresholdData, presig.into());
+                    Ok(Self {
+                        codec: Codec::Es256Msig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                lamport::ALGORITHM_NAME => {
+                    let sig_combined = lamport::SigCombined::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, sig_combined.0.into());
+                    attributes.insert(AttrId::SigData, sig_combined.1);
+                    Ok(Self {
+                        codec: Codec::LamportMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                bbs::ALGORITHM_NAME => {
+                    let sig_bbs = bbs::SigBbs::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, sig_bbs.0.into());
+                    if !sig_bbs.1.is_empty() {
+                        attributes.insert(AttrId::Context, sig_bbs.1);
+                    }
+                    attributes.insert(AttrId::SigData, sig_bbs.2);
+                    Ok(Self {
+                        codec: Codec::Bls12381G1Msig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                bbs::ALGORITHM_NAME_PROOF => {
+                    let sig_proof = bbs::SigProof::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, sig_proof.0.into());
+                    if !sig_proof.1.is_empty() {
+                        attributes.insert(AttrId::Context, sig_proof.1);
+                    }
+                    if !sig_proof.2.is_empty() {
+                        attributes.insert(AttrId::PresentationHeader, sig_proof.2);
+                    }
+                    attributes.insert(AttrId::DisclosedIndexes, sig_proof.3.into());
+                    attributes.insert(AttrId::SigData, sig_proof.4);
+                    Ok(Self {
+                        codec: Codec::Bls12381G1Msig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                xmss::ALGORITHM_NAME => {
+                    let sig_stateful = xmss::SigStateful::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::Scheme, sig_stateful.0.into());
+                    attributes.insert(AttrId::LeafIndex, Varuint(sig_stateful.1).into());
+                    attributes.insert(AttrId::TreeHeight, Varuint(sig_stateful.2).into());
+                    attributes.insert(AttrId::SigData, sig_stateful.3);
+                    Ok(Self {
+                        codec: Codec::LamportMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
+                lamport::ALGORITHM_NAME_SHARE => {
+                    let sig_share = lamport::SigShare::try_from(sig.as_bytes())?;
+                    attributes.insert(AttrId::ShareIdentifier, Varuint(sig_share.0).into());
+                    attributes.insert(AttrId::Threshold, Varuint(sig_share.1).into());
+                    attributes.insert(AttrId::Limit, Varuint(sig_share.2).into());
+                    attributes.insert(AttrId::Scheme, sig_share.3.into());
+                    attributes.insert(AttrId::SigData, sig_share.4);
+                    Ok(Self {
+                        codec: Codec::LamportShareMsig,
+                        attributes: Some(attributes),
+                        ..Default::default()
+                    })
+                }
                 _ => Err(Error::UnsupportedAlgorithm(name.as_str().to_string())),
             },
             _ => Err(Error::UnsupportedAlgorithm(sig.algorithm().to_string())),
         }
     }
 
-    /// create a new builder from a Bls Signature
-    pub fn new_from_bls_signature<C>(sig: &Signature<C>) -> Result<Self, Error>
-    where
-        C: blsful::BlsSignatureImpl,
-    {
-        let scheme_type_id = SchemeTypeId::from(sig);
-        let sig_bytes: Vec<u8> = sig.as_raw_value().to_bytes().as_ref().to_vec();
-        println!("signature length: {}", sig_bytes.len());
-        let codec = match sig_bytes.len() {
-            48 => Codec::Bls12381G1Msig, // G1Projective::to_compressed()
-            96 => Codec::Bls12381G2Msig, // G2Projective::to_compressed()
-            _ => {
-                return Err(Error::UnsupportedAlgorithm(
-                    "invalid Bls signature size".to_string(),
-                ))
-            }
-        };
+    /// create a new builder for an Ed25519 half-aggregate signature from the
+    /// Ed25519 signatures and the signers' public keys, in the same order.
+    /// every signature must carry the message that it signs.
+    pub fn new_from_ed25519_signatures(
+        sigs: &[Multisig],
+        public_keys: &[impl AsRef<[u8]>],
+    ) -> Result<Self, Error> {
+        let keys: Vec<Vec<u8>> = public_keys.iter().map(|k| k.as_ref().to_vec()).collect();
+        let (sig_bytes, messages) = ed25519_halfagg::half_aggregate(sigs, &keys)?;
         let mut attributes = BTreeMap::new();
+        attributes.insert(
+            AttrId::Scheme,
+            eddsa::SchemeTypeId::Ed25519HalfAggregate.into(),
+        );
         attributes.insert(AttrId::SigData, sig_bytes);
-        attributes.insert(AttrId::Scheme, scheme_type_id.into());
+        attributes.insert(AttrId::SignerCount, Varuint(keys.len()).into());
+        attributes.insert(AttrId::SignerKeyIds, aggregate::SignerKeyIds(keys).into());
+        attributes.insert(AttrId::Messages, messages.into());
+        Ok(Self {
+            codec: Codec::EddsaMsig,
+            attributes: Some(attributes),
+            ..Default::default()
+        })
+    }
+
+    /// create a new builder for an ECDSA signature from its strict ASN.1 DER
+    /// SEQUENCE { r, s } encoding. the signature is stored as the raw r||s
+    /// bytes and the DER encoding is recorded.
+    pub fn new_from_der_signature(codec: Codec, der: &[u8]) -> Result<Self, Error> {
+        let sig_bytes = ecdsa::from_der(der, ecdsa::scalar_size(codec)?)?;
+        let mut attributes = BTreeMap::new();
+        attributes.insert(AttrId::SigData, sig_bytes);
+        attributes.insert(AttrId::SigEncoding, ecdsa::SignatureEncoding::Der.into());
         Ok(Self {
             codec,
             attributes: Some(attributes),
@@ -354,12 +895,77 @@ impl Builder {
         })
     }
 
-    /// create a new builder from a Bls SignatureShare
-    pub fn new_from_bls_signature_share<C>(
-        threshold: usize,
-        limit: usize,
-        sigshare: &SignatureShare<C>,
-    ) -> Result<Self, Error>
+    /// create a new builder for an Ed448 signature from an SSH signature blob
+    /// named either ssh-ed448 (RFC 8709) or ed448@multisig
+    pub fn new_from_ssh_ed448_signature(blob: &[u8]) -> Result<Self, Error> {
+        let ed448::SigContext(context, sig_bytes) = ed448::from_ssh_ed448_signature(blob)?;
+        let mut attributes = BTreeMap::new();
+        attributes.insert(AttrId::Scheme, eddsa::SchemeTypeId::Ed448.into());
+        attributes.insert(AttrId::SigData, sig_bytes);
+        if !context.is_empty() {
+            attributes.insert(AttrId::Context, context);
+        }
+        Ok(Self {
+            codec: Codec::EddsaMsig,
+            attributes: Some(attributes),
+            ..Default::default()
+        })
+    }
+
+    /// create a new builder for a recoverable secp256k1 signature from its
+    /// 65-byte r||s||v form. EIP-155 v values also record the chain id.
+    pub fn new_from_secp256k1_rsv(rsv: &[u8]) -> Result<Self, Error> {
+        let (sig_bytes, recovery_id, chain_id) = secp256k1::from_rsv(rsv)?;
+        let mut attributes = BTreeMap::new();
+        attributes.insert(
+            AttrId::Scheme,
+            secp256k1::SchemeTypeId::EcdsaRecoverable.into(),
+        );
+        attributes.insert(AttrId::SigData, sig_bytes);
+        attributes.insert(AttrId::RecoveryId, Varuint(recovery_id).into());
+        if let Some(chain_id) = chain_id {
+            attributes.insert(AttrId::ChainId, Varuint(chain_id).into());
+        }
+        Ok(Self {
+            codec: Codec::Es256KMsig,
+            attributes: Some(attributes),
+            ..Default::default()
+        })
+    }
+
+    /// create a new builder from a Bls Signature
+    pub fn new_from_bls_signature<C>(sig: &Signature<C>) -> Result<Self, Error>
+    where
+        C: blsful::BlsSignatureImpl,
+    {
+        let scheme_type_id = SchemeTypeId::from(sig);
+        let sig_bytes: Vec<u8> = sig.as_raw_value().to_bytes().as_ref().to_vec();
+        println!("signature length: {}", sig_bytes.len());
+        let codec = match sig_bytes.len() {
+            48 => Codec::Bls12381G1Msig, // G1Projective::to_compressed()
+            96 => Codec::Bls12381G2Msig, // G2Projective::to_compressed()
+            _ => {
+                return Err(Error::UnsupportedAlgorithm(
+                    "invalid Bls signature size".to_string(),
+                ))
+            }
+        };
+        let mut attributes = BTreeMap::new();
+        attributes.insert(AttrId::SigData, sig_bytes);
+        attributes.insert(AttrId::Scheme, scheme_type_id.into());
+        Ok(Self {
+            codec,
+            attributes: Some(attributes),
+            ..Default::default()
+        })
+    }
+
+    /// create a new builder from a Bls SignatureShare
+    pub fn new_from_bls_signature_share<C>(
+        threshold: usize,
+        limit: usize,
+        sigshare: &SignatureShare<C>,
+    ) -> Result<Self, Error>
     where
         C: blsful::BlsSignatureImpl,
     {
@@ -425,6 +1031,16 @@ impl Builder {
         self.with_attribute(AttrId::SigData, &data.as_ref().to_vec())
     }
 
+    /// add the signing context string
+    pub fn with_context(self, context: &impl AsRef<[u8]>) -> Self {
+        self.with_attribute(AttrId::Context, &context.as_ref().to_vec())
+    }
+
+    /// add the multi-party signature public nonce
+    pub fn with_public_nonce(self, nonce: &impl AsRef<[u8]>) -> Self {
+        self.with_attribute(AttrId::PublicNonce, &nonce.as_ref().to_vec())
+    }
+
     /// add the threshold signature threshold
     pub fn with_threshold(self, threshold: usize) -> Self {
         self.with_attribute(AttrId::Threshold, &Varuint(threshold).into())
@@ -445,6 +1061,72 @@ impl Builder {
         self.with_attribute(AttrId::ThresholdData, &tdata.as_ref().to_vec())
     }
 
+    /// add the aggregate signature signer count
+    pub fn with_signer_count(self, count: usize) -> Self {
+        self.with_attribute(AttrId::SignerCount, &Varuint(count).into())
+    }
+
+    /// add the aggregate signature signer key identifiers
+    pub fn with_signer_key_ids(self, key_ids: &impl AsRef<[u8]>) -> Self {
+        self.with_attribute(AttrId::SignerKeyIds, &key_ids.as_ref().to_vec())
+    }
+
+    /// add the aggregate signature messages
+    pub fn with_messages(self, messages: &impl AsRef<[u8]>) -> Self {
+        self.with_attribute(AttrId::Messages, &messages.as_ref().to_vec())
+    }
+
+    /// set the encoding the signature data arrived in
+    pub fn with_signature_encoding(self, encoding: ecdsa::SignatureEncoding) -> Self {
+        self.with_attribute(AttrId::SigEncoding, &encoding.into())
+    }
+
+    /// add the recoverable signature recovery id
+    pub fn with_recovery_id(self, recovery_id: u8) -> Self {
+        self.with_attribute(AttrId::RecoveryId, &Varuint(recovery_id).into())
+    }
+
+    /// add the recoverable signature EIP-155 chain id
+    pub fn with_chain_id(self, chain_id: u64) -> Self {
+        self.with_attribute(AttrId::ChainId, &Varuint(chain_id).into())
+    }
+
+    /// set the message field to a vector of messages, such as the messages
+    /// signed by a BBS signature or disclosed by a BBS proof
+    pub fn with_message_vector(self, messages: &[Vec<u8>]) -> Self {
+        let m: Vec<u8> = aggregate::Messages(messages.to_vec()).into();
+        self.with_message_bytes(&m)
+    }
+
+    /// add the indexes of the messages disclosed by a selective disclosure
+    /// proof
+    pub fn with_disclosed_indexes(self, indexes: &[usize]) -> Self {
+        self.with_attribute(
+            AttrId::DisclosedIndexes,
+            &bbs::DisclosedIndexes(indexes.to_vec()).into(),
+        )
+    }
+
+    /// add the selective disclosure proof presentation header
+    pub fn with_presentation_header(self, header: &impl AsRef<[u8]>) -> Self {
+        self.with_attribute(AttrId::PresentationHeader, &header.as_ref().to_vec())
+    }
+
+    /// add the stateful hash-based signature one-time leaf index
+    pub fn with_leaf_index(self, leaf_index: u64) -> Self {
+        self.with_attribute(AttrId::LeafIndex, &Varuint(leaf_index).into())
+    }
+
+    /// add the stateful hash-based signature tree height
+    pub fn with_tree_height(self, tree_height: u8) -> Self {
+        self.with_attribute(AttrId::TreeHeight, &Varuint(tree_height).into())
+    }
+
+    /// add the RSA modulus size in bytes
+    pub fn with_modulus_size(self, modulus_size: usize) -> Self {
+        self.with_attribute(AttrId::ModulusSize, &Varuint(modulus_size).into())
+    }
+
     /// add a signature share
     pub fn add_signature_share(mut self, share: &Multisig) -> Self {
         let mut shares = self.shares.unwrap_or_default();
@@ -453,6 +1135,23 @@ impl Builder {
         self
     }
 
+    /// add a signature from a distinct signer to aggregate
+    pub fn add_signature(mut self, key_id: &impl AsRef<[u8]>, sig: &Multisig) -> Self {
+        let mut signatures = self.signatures.unwrap_or_default();
+        signatures.push((key_id.as_ref().to_vec(), sig.clone()));
+        self.signatures = Some(signatures);
+        self
+    }
+
+    /// add a signature over its own message to aggregate. the signature must
+    /// carry the message, or message digest, that it signs
+    pub fn add_message_signature(mut self, key_id: &impl AsRef<[u8]>, sig: &Multisig) -> Self {
+        let mut message_signatures = self.message_signatures.unwrap_or_default();
+        message_signatures.push((key_id.as_ref().to_vec(), sig.clone()));
+        self.message_signatures = Some(message_signatures);
+        self
+    }
+
     /// build a base encoded varsig
     pub fn try_build_encoded(self) -> Result<EncodedMultisig, Error> {
         Ok(BaseEncoded::new(
@@ -479,16 +1178,31 @@ impl Builder {
                     tv.add_share(share)?
                 };
             }
-            Ok(ms)
-        } else {
-            Ok(ms)
         }
+        if let Some(signatures) = self.signatures {
+            for (key_id, sig) in &signatures {
+                ms = {
+                    let av = ms.aggregate_view()?;
+                    av.add_signature(key_id, sig)?
+                };
+            }
+        }
+        if let Some(message_signatures) = self.message_signatures {
+            for (key_id, sig) in &message_signatures {
+                ms = {
+                    let av = ms.aggregate_view()?;
+                    av.add_message_signature(key_id, sig)?
+                };
+            }
+        }
+        Ok(ms)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::AggregateError;
 
     #[test]
     fn test_encoded() {
@@ -524,157 +1238,1973 @@ mod tests {
     }
 
     #[test]
-    fn test_es256k() {
-        let ms = Builder::new(Codec::Es256KMsig)
-            .with_signature_bytes(&[0u8; 64])
+    fn test_ed448() {
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Ed448.into())
+            .with_signature_bytes(&[0u8; 114])
             .try_build()
             .unwrap();
         let v: Vec<u8> = ms.clone().into();
         assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        let av = ms.attr_view().unwrap();
+        assert_eq!(eddsa::SchemeTypeId::Ed448.code(), av.scheme().unwrap());
+        let dv = ms.data_view().unwrap();
+        assert_eq!(114, dv.sig_bytes().unwrap().len());
     }
 
     #[test]
-    fn test_bls_signature() {
-        let sk = blsful::Bls12381G2::new_secret_key();
-        let sig = sk
-            .sign(
-                blsful::SignatureSchemes::ProofOfPossession,
-                b"for great justice, move every zig!",
-            )
-            .unwrap();
-
-        let ms = Builder::new_from_bls_signature(&sig)
-            .unwrap()
+    fn test_ed448_invalid_length() {
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Ed448.into())
+            .with_signature_bytes(&[0u8; 64])
             .try_build()
             .unwrap();
-
-        let v: Vec<u8> = ms.clone().into();
-        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        let dv = ms.data_view().unwrap();
+        assert!(dv.sig_bytes().is_err());
     }
 
     #[test]
-    fn test_bls_signature_combine() {
-        let sk = blsful::Bls12381G2::new_secret_key();
-        let sig = sk
-            .sign(
-                blsful::SignatureSchemes::ProofOfPossession,
-                b"for great justice, move every zig!",
-            )
+    fn test_ed25519_variants() {
+        let pure = Builder::new(Codec::EddsaMsig)
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
             .unwrap();
-
-        let ms1 = Builder::new_from_bls_signature(&sig)
-            .unwrap()
+        let ph = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Ed25519ph.into())
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        let ctx = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Ed25519ctx.into())
+            .with_context(b"for great justice")
+            .with_signature_bytes(&[0u8; 64])
             .try_build()
             .unwrap();
 
-        let sk_shares = sk.split(3, 4).unwrap();
-
-        let mut sigs = Vec::default();
-        sk_shares.iter().for_each(|sk| {
-            let sig = sk
-                .sign(
-                    blsful::SignatureSchemes::ProofOfPossession,
-                    b"for great justice, move every zig!",
-                )
-                .unwrap();
-            sigs.push(
-                Builder::new_from_bls_signature_share(3, 4, &sig)
-                    .unwrap()
-                    .try_build()
-                    .unwrap(),
-            );
-        });
+        // each variant has its own scheme
+        let schemes: Vec<u8> = [&pure, &ph, &ctx]
+            .iter()
+            .map(|ms| ms.attr_view().unwrap().scheme().unwrap())
+            .collect();
+        assert_eq!(
+            vec![
+                eddsa::SchemeTypeId::Ed25519.code(),
+                eddsa::SchemeTypeId::Ed25519ph.code(),
+                eddsa::SchemeTypeId::Ed25519ctx.code()
+            ],
+            schemes
+        );
+        assert!(pure.attr_view().unwrap().context().is_err());
+        assert!(ph.attr_view().unwrap().context().unwrap().is_empty());
+        assert_eq!(
+            b"for great justice".to_vec(),
+            ctx.attr_view().unwrap().context().unwrap()
+        );
+        let v: Vec<u8> = ctx.clone().into();
+        assert_eq!(ctx, Multisig::try_from(v.as_slice()).unwrap());
 
-        // build a new signature from the parts
-        let mut builder = Builder::new(Codec::Bls12381G2Msig);
-        for sig in &sigs {
-            builder = builder.add_signature_share(sig);
-        }
-        let ms2 = builder.try_build().unwrap();
+        // pure Ed25519 never has a context and Ed25519ctx always has one
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_context(b"for great justice")
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Ed25519ctx.into())
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+    }
 
-        let av = ms2.threshold_attr_view().unwrap();
-        assert_eq!(3, av.threshold().unwrap());
-        assert_eq!(4, av.limit().unwrap());
+    #[test]
+    fn test_sr25519() {
+        let mut sig = [0x2au8; 64];
+        sig[63] |= 0x80;
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Sr25519.into())
+            .with_context(b"substrate")
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        let av = ms.attr_view().unwrap();
+        assert_eq!(eddsa::SchemeTypeId::Sr25519.code(), av.scheme().unwrap());
+        assert_eq!(b"substrate".to_vec(), av.context().unwrap());
+        assert_eq!(sig.to_vec(), ms.data_view().unwrap().sig_bytes().unwrap());
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
 
-        let tv = ms2.threshold_view().unwrap();
-        let ms3 = tv.combine().unwrap();
+        // the signing context label is required
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Sr25519.into())
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
 
-        assert_eq!(ms1, ms3);
+        // an Ed25519 signature without the schnorrkel marker is rejected
+        sig[63] &= 0x7f;
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Sr25519.into())
+            .with_context(b"substrate")
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
     }
 
     #[test]
-    fn test_eddsa_ssh_roundtrip() {
-        let ms1 = Builder::new(Codec::EddsaMsig)
-            .with_signature_bytes(&[0u8; 64])
-            .try_build()
-            .unwrap();
-        let cv = ms1.conv_view().unwrap();
-        let ms_ssh = cv.to_ssh_signature().unwrap();
-        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
-            .unwrap()
+    fn test_sr25519_not_eddsa() {
+        let mut sig = [0x2au8; 64];
+        sig[63] |= 0x80;
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Sr25519.into())
+            .with_context(b"substrate")
+            .with_signature_bytes(&sig)
             .try_build()
             .unwrap();
-        assert_eq!(ms1, ms2);
+
+        // sr25519 signatures are never handled as EdDSA signatures
+        assert!(ed25519::View::try_from(&ms).is_err());
+        assert!(ms.threshold_attr_view().is_err());
+        assert!(ms.threshold_view().is_err());
+        assert!(ms.aggregate_view().is_err());
+
+        // and cannot be half-aggregated with Ed25519 signatures
+        let (mut keys, mut sigs) = ed25519_signatures(1);
+        keys.push(vec![0u8; 32]);
+        sigs.push(ms);
+        assert!(Builder::new_from_ed25519_signatures(&sigs, &keys).is_err());
     }
 
     #[test]
-    fn test_es256k_ssh_roundtrip() {
-        let ms1 = Builder::new(Codec::Es256KMsig)
+    fn test_xeddsa() {
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::XEdDsa.into())
             .with_signature_bytes(&[0u8; 64])
             .try_build()
             .unwrap();
-        let cv = ms1.conv_view().unwrap();
-        let ms_ssh = cv.to_ssh_signature().unwrap();
-        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
-            .unwrap()
+
+        // XEdDSA signatures are never mistaken for plain Ed25519 signatures
+        let av = ms.attr_view().unwrap();
+        assert_eq!(eddsa::SchemeTypeId::XEdDsa.code(), av.scheme().unwrap());
+        assert_ne!(eddsa::SchemeTypeId::Ed25519.code(), av.scheme().unwrap());
+        assert!(av.context().is_err());
+        assert_eq!(64, ms.data_view().unwrap().sig_bytes().unwrap().len());
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::XEdDsa.into())
+            .with_signature_bytes(&[0u8; 63])
             .try_build()
             .unwrap();
-        assert_eq!(ms1, ms2);
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+    }
+
+    fn ed25519_signatures(n: u8) -> (Vec<Vec<u8>>, Vec<Multisig>) {
+        use ed25519_dalek::Signer;
+
+        (1..=n)
+            .map(|i| {
+                let sk = ed25519_dalek::SigningKey::from_bytes(&[i; 32]);
+                let msg = format!("audit log entry {}", i);
+                let ms = Builder::new(Codec::EddsaMsig)
+                    .with_message_bytes(&msg)
+                    .with_signature_bytes(&sk.sign(msg.as_bytes()).to_bytes())
+                    .try_build()
+                    .unwrap();
+                (sk.verifying_key().to_bytes().to_vec(), ms)
+            })
+            .unzip()
     }
 
     #[test]
-    fn test_bls_signature_ssh_roundtrip() {
-        let sk = blsful::Bls12381G1::new_secret_key();
-        let sig = sk
-            .sign(
-                blsful::SignatureSchemes::ProofOfPossession,
-                b"for great justice, move every zig!",
-            )
-            .unwrap();
+    fn test_ed25519_half_aggregate() {
+        use curve25519_dalek::{constants::ED25519_BASEPOINT_POINT, edwards::CompressedEdwardsY};
+        use sha2::{Digest, Sha512};
 
-        let ms1 = Builder::new_from_bls_signature(&sig)
+        let (keys, sigs) = ed25519_signatures(4);
+        let ms = Builder::new_from_ed25519_signatures(&sigs, &keys)
             .unwrap()
             .try_build()
             .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
 
-        let cv = ms1.conv_view().unwrap();
-        let ssh_ms = cv.to_ssh_signature().unwrap();
+        let av = ms.aggregate_view().unwrap();
+        assert_eq!(4, av.signer_count().unwrap());
+        assert_eq!(keys, av.signer_key_ids().unwrap());
+        let messages = av.messages().unwrap();
+        assert_eq!(
+            sigs.iter().map(|s| s.message.clone()).collect::<Vec<_>>(),
+            messages
+        );
 
-        let ms2 = Builder::new_from_ssh_signature(&ssh_ms)
+        // the R values and one s value take about half the space
+        let dv = ms.data_view().unwrap();
+        let agg = dv.sig_bytes().unwrap();
+        assert_eq!(5 * 32, agg.len());
+
+        // s * B == sum(z_i * (R_i + c_i * A_i))
+        let point = |b: &[u8]| {
+            CompressedEdwardsY(b.try_into().unwrap())
+                .decompress()
+                .unwrap()
+        };
+        let rs: Vec<&[u8]> = agg[..4 * 32].chunks(32).collect();
+        let zs = ed25519_halfagg::coefficients(&rs, &keys, &messages);
+        let mut rhs = curve25519_dalek::EdwardsPoint::default();
+        for (i, z) in zs.iter().enumerate() {
+            let mut hasher = Sha512::new();
+            hasher.update(rs[i]);
+            hasher.update(&keys[i]);
+            hasher.update(&messages[i]);
+            let c = curve25519_dalek::Scalar::from_bytes_mod_order_wide(&hasher.finalize().into());
+            rhs += z * (point(rs[i]) + c * point(&keys[i]));
+        }
+        let s = curve25519_dalek::Scalar::from_canonical_bytes(agg[4 * 32..].try_into().unwrap())
+            .unwrap();
+        assert_eq!(ED25519_BASEPOINT_POINT * s, rhs);
+        assert_eq!(curve25519_dalek::Scalar::ONE, zs[0]);
+    }
+
+    #[test]
+    fn test_ed25519_half_aggregate_verify() {
+        let (keys, sigs) = ed25519_signatures(3);
+        let ms = Builder::new_from_ed25519_signatures(&sigs, &keys)
             .unwrap()
             .try_build()
             .unwrap();
+        assert!(ed25519_halfagg::verify(&ms).is_ok());
 
-        assert_eq!(ms1, ms2);
+        // round trip through the binary encoding
+        let v: Vec<u8> = ms.clone().into();
+        let ms2 = Multisig::try_from(v.as_slice()).unwrap();
+        assert!(ed25519_halfagg::verify(&ms2).is_ok());
+
+        // swapping two R values breaks the aggregate
+        let mut swapped = ms.clone();
+        let agg = swapped.attributes.get_mut(&AttrId::SigData).unwrap();
+        let (r1, r2) = agg.split_at_mut(32);
+        r1.swap_with_slice(&mut r2[..32]);
+        assert!(ed25519_halfagg::verify(&swapped).is_err());
+
+        // so does changing one of the signed messages
+        let mut tampered = ms.clone();
+        let mut messages = ms.aggregate_view().unwrap().messages().unwrap();
+        messages[2] = b"audit log entry 4".to_vec();
+        tampered
+            .attributes
+            .insert(AttrId::Messages, aggregate::Messages(messages).into());
+        assert!(ed25519_halfagg::verify(&tampered).is_err());
+
+        // a single Ed25519 signature is not a half-aggregate
+        assert!(ed25519_halfagg::verify(&sigs[0]).is_err());
     }
 
     #[test]
-    fn test_bls_signature_combine_ssh_roundtrip() {
-        let sk = blsful::Bls12381G2::new_secret_key();
-        let sig = sk
-            .sign(
-                blsful::SignatureSchemes::ProofOfPossession,
-                b"for great justice, move every zig!",
-            )
-            .unwrap();
+    fn test_ed25519_half_aggregate_invalid() {
+        let (keys, mut sigs) = ed25519_signatures(2);
 
-        let ms1 = Builder::new_from_bls_signature(&sig)
-            .unwrap()
+        // the keys must match the signatures
+        assert!(matches!(
+            Builder::new_from_ed25519_signatures(&sigs, &keys[..1]),
+            Err(Error::Aggregate(AggregateError::SignerCountMismatch))
+        ));
+
+        // every signature must carry its message
+        let mut detached = sigs.clone();
+        detached[1].message.clear();
+        assert!(matches!(
+            Builder::new_from_ed25519_signatures(&detached, &keys),
+            Err(Error::Aggregate(AggregateError::MissingMessage))
+        ));
+
+        // only pure Ed25519 signatures can be half-aggregated
+        sigs[1]
+            .attributes
+            .insert(AttrId::Scheme, eddsa::SchemeTypeId::Ed25519ph.into());
+        assert!(matches!(
+            Builder::new_from_ed25519_signatures(&sigs, &keys),
+            Err(Error::Aggregate(AggregateError::SchemeMismatch))
+        ));
+        sigs[1] = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(b"audit log entry 2")
+            .with_signature_bytes(&[0u8; 64])
             .try_build()
             .unwrap();
+        assert!(matches!(
+            Builder::new_from_ed25519_signatures(&sigs, &keys),
+            Err(Error::Aggregate(AggregateError::CodecMismatch(
+                Codec::Es256KMsig
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_es256k() {
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_es256k_schnorr() {
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_scheme(secp256k1::SchemeTypeId::Schnorr.into())
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        let av = ms.attr_view().unwrap();
+        assert_eq!(
+            secp256k1::SchemeTypeId::Schnorr.code(),
+            av.scheme().unwrap()
+        );
+
+        // plain Es256K multisigs are ECDSA
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        let av = ms.attr_view().unwrap();
+        assert_eq!(secp256k1::SchemeTypeId::Ecdsa.code(), av.scheme().unwrap());
+    }
+
+    #[test]
+    fn test_es256k_schnorr_out_of_range() {
+        // s is greater than the group order
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_scheme(secp256k1::SchemeTypeId::Schnorr.into())
+            .with_signature_bytes(&[0xffu8; 64])
+            .try_build()
+            .unwrap();
+        let dv = ms.data_view().unwrap();
+        assert!(dv.sig_bytes().is_err());
+    }
+
+    fn secp256k1_recoverable(msg: &[u8]) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        use k256::ecdsa::SigningKey;
+        use sha2::{Digest, Sha256};
+
+        let sk = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let digest = Sha256::digest(msg).to_vec();
+        let (sig, recovery_id) = sk.sign_prehash_recoverable(&digest).unwrap();
+        let public_key = sk
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let mut rsv = sig.to_bytes().to_vec();
+        rsv.push(recovery_id.to_byte());
+        (digest, rsv, public_key)
+    }
+
+    #[test]
+    fn test_es256k_recoverable() {
+        let (digest, rsv, public_key) = secp256k1_recoverable(b"for great justice");
+        let recovery_id = rsv[64];
+
+        // raw, legacy and EIP-155 v values for chain id 1
+        for (v, chain_id) in [
+            (recovery_id, None),
+            (recovery_id + 27, None),
+            (recovery_id + 37, Some(1)),
+        ] {
+            let mut bytes = rsv.clone();
+            bytes[64] = v;
+            let ms = Builder::new_from_secp256k1_rsv(&bytes)
+                .unwrap()
+                .try_build()
+                .unwrap();
+            let v: Vec<u8> = ms.clone().into();
+            assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+
+            let av = ms.attr_view().unwrap();
+            assert_eq!(
+                secp256k1::SchemeTypeId::EcdsaRecoverable.code(),
+                av.scheme().unwrap()
+            );
+            let rv = ms.recovery_view().unwrap();
+            assert_eq!(recovery_id, rv.recovery_id().unwrap());
+            assert_eq!(chain_id, rv.chain_id().ok());
+            assert_eq!(public_key, rv.recover_public_key(&digest).unwrap());
+
+            // raw v values are exported as legacy v values
+            let mut expected = bytes.clone();
+            if chain_id.is_none() {
+                expected[64] = recovery_id + 27;
+            }
+            assert_eq!(expected, rv.to_rsv().unwrap());
+        }
+
+        // a different digest recovers a different key
+        let ms = Builder::new_from_secp256k1_rsv(&rsv)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        let rv = ms.recovery_view().unwrap();
+        assert_ne!(public_key, rv.recover_public_key(&[0x42u8; 32]).unwrap());
+        assert!(rv.recover_public_key(&digest[..31]).is_err());
+    }
+
+    #[test]
+    fn test_es256k_recoverable_invalid() {
+        let (_, mut rsv, _) = secp256k1_recoverable(b"for great justice");
+
+        // v values between the legacy and EIP-155 ranges are invalid
+        rsv[64] = 31;
+        assert!(Builder::new_from_secp256k1_rsv(&rsv).is_err());
+        assert!(Builder::new_from_secp256k1_rsv(&rsv[..64]).is_err());
+
+        // the recovery id is required
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_scheme(secp256k1::SchemeTypeId::EcdsaRecoverable.into())
+            .with_signature_bytes(&rsv[..64].to_vec())
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+
+        // the chain id must fit in the single byte v value
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_scheme(secp256k1::SchemeTypeId::EcdsaRecoverable.into())
+            .with_signature_bytes(&rsv[..64].to_vec())
+            .with_recovery_id(0)
+            .with_chain_id(137)
+            .try_build()
+            .unwrap();
+        assert!(ms.recovery_view().unwrap().to_rsv().is_err());
+    }
+
+    #[test]
+    fn test_ecdsa_der() {
+        use sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(b"for great justice");
+        let sk = ::p256::ecdsa::SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let (p256_sig, _) = sk.sign_prehash_recoverable(&digest).unwrap();
+        let sk = k256::ecdsa::SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let (k256_sig, _) = sk.sign_prehash_recoverable(&digest).unwrap();
+
+        for (codec, raw, der) in [
+            (
+                Codec::Es256Msig,
+                p256_sig.to_bytes().to_vec(),
+                p256_sig.to_der().as_bytes().to_vec(),
+            ),
+            (
+                Codec::Es256KMsig,
+                k256_sig.to_bytes().to_vec(),
+                k256_sig.to_der().as_bytes().to_vec(),
+            ),
+        ] {
+            // DER signatures are stored as raw r||s and remember their encoding
+            let ms = Builder::new_from_der_signature(codec, &der)
+                .unwrap()
+                .try_build()
+                .unwrap();
+            assert_eq!(raw, ms.data_view().unwrap().sig_bytes().unwrap());
+            let ev = ms.ecdsa_view().unwrap();
+            assert_eq!(
+                ecdsa::SignatureEncoding::Der,
+                ev.signature_encoding().unwrap()
+            );
+            assert_eq!(der, ev.to_der().unwrap());
+            let v: Vec<u8> = ms.clone().into();
+            assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+
+            // raw signatures export the same DER encoding
+            let ms = Builder::new(codec)
+                .with_signature_bytes(&raw)
+                .try_build()
+                .unwrap();
+            let ev = ms.ecdsa_view().unwrap();
+            assert_eq!(
+                ecdsa::SignatureEncoding::Raw,
+                ev.signature_encoding().unwrap()
+            );
+            assert_eq!(der, ev.to_der().unwrap());
+        }
+
+        // P-384 and P-521 signatures, the latter needing a long form length
+        let mut raw = vec![0x42u8; 2 * p384::SCALAR_SIZE];
+        raw[0] = 0x80;
+        raw[p384::SCALAR_SIZE] = 0;
+        let mut raw521 = vec![0x42u8; 2 * p521::SCALAR_SIZE];
+        raw521[0] = 1;
+        raw521[p521::SCALAR_SIZE] = 0;
+        for (codec, raw) in [(Codec::Es384Msig, raw), (Codec::Es521Msig, raw521)] {
+            let ms = Builder::new(codec)
+                .with_signature_bytes(&raw)
+                .try_build()
+                .unwrap();
+            let der = ms.ecdsa_view().unwrap().to_der().unwrap();
+            let ms = Builder::new_from_der_signature(codec, &der)
+                .unwrap()
+                .try_build()
+                .unwrap();
+            assert_eq!(raw, ms.data_view().unwrap().sig_bytes().unwrap());
+        }
+
+        // only ECDSA codecs and schemes have a DER encoding
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_signature_bytes(&[0x42u8; 64])
+            .try_build()
+            .unwrap();
+        assert!(ms.ecdsa_view().is_err());
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_scheme(secp256k1::SchemeTypeId::Schnorr.into())
+            .with_signature_bytes(&[0x42u8; 64])
+            .try_build()
+            .unwrap();
+        assert!(ms.ecdsa_view().is_err());
+    }
+
+    #[test]
+    fn test_ecdsa_low_s() {
+        let (digest, rsv, public_key) = secp256k1_recoverable(b"for great justice");
+
+        // k256 always signs with a low s so flip it to n - s and flip the
+        // recovery id to match
+        let sig = k256::ecdsa::Signature::from_slice(&rsv[..64]).unwrap();
+        let sig = k256::ecdsa::Signature::from_scalars(sig.r(), -sig.s()).unwrap();
+        let mut high = sig.to_bytes().to_vec();
+        high.push(rsv[64] ^ 1);
+        let ms = Builder::new_from_secp256k1_rsv(&high)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        let ev = ms.ecdsa_view().unwrap();
+        assert!(!ev.is_low_s().unwrap());
+
+        // the normalized copy is the original signature and still recovers
+        // the signer's key
+        let normalized = ev.normalize_s().unwrap();
+        let ev = normalized.ecdsa_view().unwrap();
+        assert!(ev.is_low_s().unwrap());
+        let rv = normalized.recovery_view().unwrap();
+        assert_eq!(rsv[64], rv.recovery_id().unwrap());
+        assert_eq!(public_key, rv.recover_public_key(&digest).unwrap());
+        assert_eq!(normalized, ev.normalize_s().unwrap());
+
+        // strict decoding rejects the high-S signature only
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        assert!(Multisig::try_decode_strict(&v).is_err());
+        let v: Vec<u8> = normalized.clone().into();
+        let (ms, _) = Multisig::try_decode_strict(&v).unwrap();
+        assert_eq!(normalized, ms);
+
+        // the other curves use their own group order
+        for (codec, size) in [
+            (Codec::Es256Msig, p256::SCALAR_SIZE),
+            (Codec::Es384Msig, p384::SCALAR_SIZE),
+            (Codec::Es521Msig, p521::SCALAR_SIZE),
+        ] {
+            let mut sig = vec![0x01u8; 2 * size];
+            // s = n - 1 is high and normalizes to 1
+            sig[size..].copy_from_slice(match codec {
+                Codec::Es256Msig => &p256::CURVE_ORDER[..],
+                Codec::Es384Msig => &p384::CURVE_ORDER[..],
+                _ => &p521::CURVE_ORDER[..],
+            });
+            sig[2 * size - 1] -= 1;
+            let ms = Builder::new(codec)
+                .with_signature_bytes(&sig)
+                .try_build()
+                .unwrap();
+            let normalized = ms.ecdsa_view().unwrap().normalize_s().unwrap();
+            let s = normalized.data_view().unwrap().sig_bytes().unwrap();
+            assert_eq!(1, s[2 * size - 1]);
+            assert!(s[size..2 * size - 1].iter().all(|b| *b == 0));
+            let v: Vec<u8> = ms.into();
+            assert!(Multisig::try_decode_strict(&v).is_err());
+        }
+
+        // strict decoding leaves other signatures alone
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_scheme(secp256k1::SchemeTypeId::Schnorr.into())
+            .with_signature_bytes(&[0x01u8; 64])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.into();
+        assert!(Multisig::try_decode_strict(&v).is_ok());
+    }
+
+    #[test]
+    fn test_ecdsa_low_s_invalid_length() {
+        // a short secp256k1 ECDSA signature has no full-width s value
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_signature_bytes(&[0x01u8; 10])
+            .try_build()
+            .unwrap();
+        let ev = ms.ecdsa_view().unwrap();
+        assert!(matches!(
+            ev.is_low_s(),
+            Err(Error::Attributes(AttributesError::InvalidSignatureLength(
+                10
+            )))
+        ));
+        assert!(matches!(
+            ev.normalize_s(),
+            Err(Error::Attributes(AttributesError::InvalidSignatureLength(
+                10
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_ecdsa_low_s_zero_component() {
+        let sig = [0x01u8; 64];
+        for zero in [0..32, 32..64] {
+            let mut sig = sig;
+            sig[zero].fill(0);
+            let ms = Builder::new(Codec::Es256KMsig)
+                .with_signature_bytes(&sig)
+                .try_build()
+                .unwrap();
+            assert!(matches!(
+                ms.ecdsa_view().unwrap().is_low_s(),
+                Err(Error::Attributes(AttributesError::InvalidSignature(_)))
+            ));
+
+            // strict decoding rejects a zero r or s as an invalid signature
+            let v: Vec<u8> = ms.clone().into();
+            assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+            assert!(matches!(
+                Multisig::try_decode_strict(&v),
+                Err(Error::Attributes(AttributesError::InvalidSignature(_)))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_ecdsa_low_s_strict_skips_shares() {
+        // a Multisig accumulating threshold ECDSA shares has no signature yet
+        let msg = b"for great justice, move every zig!";
+        let (_, shares) = threshold_ecdsa_shares::<k256::Secp256k1>(
+            Codec::Es256KMsig,
+            secp256k1::SchemeTypeId::EcdsaShare.code(),
+            msg,
+            &[1, 2],
+        );
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(msg)
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        let (decoded, _) = Multisig::try_decode_strict(&v).unwrap();
+        assert_eq!(ms, decoded);
+
+        // nor does a Multisig with only a message
+        let ms = Builder::new(Codec::Es256Msig)
+            .with_message_bytes(msg)
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.into();
+        assert!(Multisig::try_decode_strict(&v).is_ok());
+    }
+
+    #[test]
+    fn test_ecdsa_der_strict() {
+        let der = [0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01];
+        assert!(Builder::new_from_der_signature(Codec::Es256Msig, &der).is_ok());
+        assert!(Builder::new_from_der_signature(Codec::EddsaMsig, &der).is_err());
+
+        for der in [
+            // non-minimal integer
+            vec![0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01],
+            // negative integer
+            vec![0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01],
+            // zero integer
+            vec![0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01],
+            // non-minimal length
+            vec![0x30, 0x81, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01],
+            // trailing data
+            vec![0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00],
+            // truncated
+            vec![0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01],
+        ] {
+            assert!(Builder::new_from_der_signature(Codec::Es256Msig, &der).is_err());
+        }
+
+        // integers wider than the curve scalar are rejected
+        let mut der = vec![0x30, 0x26, 0x02, 0x21];
+        der.extend_from_slice(&[0x42u8; 33]);
+        der.extend_from_slice(&[0x02, 0x01, 0x01]);
+        assert!(Builder::new_from_der_signature(Codec::Es256Msig, &der).is_err());
+    }
+
+    #[test]
+    fn test_es256() {
+        let ms = Builder::new(Codec::Es256Msig)
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_es384() {
+        let ms = Builder::new(Codec::Es384Msig)
+            .with_signature_bytes(&[0u8; 96])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_es521() {
+        let ms = Builder::new(Codec::Es521Msig)
+            .with_signature_bytes(&[0u8; 132])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_rs256() {
+        let ms = Builder::new(Codec::Rs256Msig)
+            .with_scheme(rsa::SchemeTypeId::PssSha256.into())
+            .with_modulus_size(256)
+            .with_signature_bytes(&[0u8; 256])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        let av = ms.attr_view().unwrap();
+        let scheme = rsa::SchemeTypeId::try_from(av.scheme().unwrap()).unwrap();
+        assert!(scheme.is_pss());
+        assert_eq!(Codec::Sha2256, scheme.hash());
+    }
+
+    #[test]
+    fn test_rs256_default_scheme() {
+        let ms = Builder::new(Codec::Rs256Msig)
+            .with_modulus_size(384)
+            .with_signature_bytes(&[0u8; 384])
+            .try_build()
+            .unwrap();
+        let av = ms.attr_view().unwrap();
+        assert_eq!(
+            rsa::SchemeTypeId::Pkcs1v15Sha256.code(),
+            av.scheme().unwrap()
+        );
+        assert_eq!(384, ms.data_view().unwrap().sig_bytes().unwrap().len());
+    }
+
+    #[test]
+    fn test_rs256_invalid_length() {
+        // the modulus size must be recorded
+        let ms = Builder::new(Codec::Rs256Msig)
+            .with_scheme(rsa::SchemeTypeId::Pkcs1v15Sha256.into())
+            .with_signature_bytes(&[0u8; 256])
+            .try_build()
+            .unwrap();
+        assert!(matches!(
+            ms.data_view().unwrap().sig_bytes(),
+            Err(Error::Attributes(AttributesError::MissingModulusSize))
+        ));
+
+        // and must be one of the supported sizes
+        let ms = Builder::new(Codec::Rs256Msig)
+            .with_modulus_size(257)
+            .with_signature_bytes(&[0u8; 257])
+            .try_build()
+            .unwrap();
+        assert!(matches!(
+            ms.data_view().unwrap().sig_bytes(),
+            Err(Error::Attributes(AttributesError::InvalidModulusSize(257)))
+        ));
+
+        // the signature must be exactly as long as the modulus
+        for size in rsa::MODULUS_SIZES {
+            for len in rsa::MODULUS_SIZES {
+                let ms = Builder::new(Codec::Rs256Msig)
+                    .with_scheme(rsa::SchemeTypeId::PssSha512.into())
+                    .with_modulus_size(size)
+                    .with_signature_bytes(&vec![0u8; len])
+                    .try_build()
+                    .unwrap();
+                let dv = ms.data_view().unwrap();
+                if size == len {
+                    assert_eq!(len, dv.sig_bytes().unwrap().len());
+                } else {
+                    assert!(matches!(
+                        dv.sig_bytes(),
+                        Err(Error::Attributes(AttributesError::InvalidSignatureLength(
+                            _
+                        )))
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bls_signature() {
+        let sk = blsful::Bls12381G2::new_secret_key();
+        let sig = sk
+            .sign(
+                blsful::SignatureSchemes::ProofOfPossession,
+                b"for great justice, move every zig!",
+            )
+            .unwrap();
+
+        let ms = Builder::new_from_bls_signature(&sig)
+            .unwrap()
+            .try_build()
+            .unwrap();
+
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_bls_signature_combine() {
+        let sk = blsful::Bls12381G2::new_secret_key();
+        let sig = sk
+            .sign(
+                blsful::SignatureSchemes::ProofOfPossession,
+                b"for great justice, move every zig!",
+            )
+            .unwrap();
+
+        let ms1 = Builder::new_from_bls_signature(&sig)
+            .unwrap()
+            .try_build()
+            .unwrap();
+
+        let sk_shares = sk.split(3, 4).unwrap();
+
+        let mut sigs = Vec::default();
+        sk_shares.iter().for_each(|sk| {
+            let sig = sk
+                .sign(
+                    blsful::SignatureSchemes::ProofOfPossession,
+                    b"for great justice, move every zig!",
+                )
+                .unwrap();
+            sigs.push(
+                Builder::new_from_bls_signature_share(3, 4, &sig)
+                    .unwrap()
+                    .try_build()
+                    .unwrap(),
+            );
+        });
+
+        // build a new signature from the parts
+        let mut builder = Builder::new(Codec::Bls12381G2Msig);
+        for sig in &sigs {
+            builder = builder.add_signature_share(sig);
+        }
+        let ms2 = builder.try_build().unwrap();
+
+        let av = ms2.threshold_attr_view().unwrap();
+        assert_eq!(3, av.threshold().unwrap());
+        assert_eq!(4, av.limit().unwrap());
+
+        let tv = ms2.threshold_view().unwrap();
+        let ms3 = tv.combine().unwrap();
+
+        assert_eq!(ms1, ms3);
+    }
+
+    fn bls_signatures(
+        scheme: blsful::SignatureSchemes,
+        msg: &[u8],
+        n: usize,
+    ) -> Vec<Signature<blsful::Bls12381G2Impl>> {
+        (0..n)
+            .map(|_| {
+                let sk = blsful::Bls12381G2::new_secret_key();
+                sk.sign(scheme, msg).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bls_signature_aggregate() {
+        let msg = b"for great justice, move every zig!";
+        let sigs = bls_signatures(blsful::SignatureSchemes::ProofOfPossession, msg, 3);
+
+        let mut builder = Builder::new(Codec::Bls12381G1Msig);
+        for (i, sig) in sigs.iter().enumerate() {
+            let ms = Builder::new_from_bls_signature(sig)
+                .unwrap()
+                .with_message_bytes(msg)
+                .try_build()
+                .unwrap();
+            builder = builder.add_signature(&[i as u8], &ms);
+        }
+        let ms = builder.try_build().unwrap();
+        assert_eq!(msg.to_vec(), ms.message);
+
+        let av = ms.aggregate_view().unwrap();
+        assert_eq!(3, av.signer_count().unwrap());
+        assert_eq!(
+            vec![vec![0u8], vec![1u8], vec![2u8]],
+            av.signer_key_ids().unwrap()
+        );
+        let av = ms.attr_view().unwrap();
+        assert_eq!(
+            bls12381::SchemeTypeId::ProofOfPossession.code(),
+            av.scheme().unwrap()
+        );
+
+        // the aggregate signature is the sum of the signatures
+        let sum = sigs
+            .iter()
+            .map(|sig| *sig.as_raw_value())
+            .reduce(|a, b| a + b)
+            .unwrap();
+        let dv = ms.data_view().unwrap();
+        assert_eq!(sum.to_bytes().as_ref().to_vec(), dv.sig_bytes().unwrap());
+
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_bls_signature_aggregate_invalid() {
+        let msg = b"for great justice, move every zig!";
+        fn build(sig: &Signature<blsful::Bls12381G2Impl>, msg: &[u8]) -> Multisig {
+            Builder::new_from_bls_signature(sig)
+                .unwrap()
+                .with_message_bytes(&msg)
+                .try_build()
+                .unwrap()
+        }
+        let pop = bls_signatures(blsful::SignatureSchemes::ProofOfPossession, msg, 2);
+        let basic = bls_signatures(blsful::SignatureSchemes::Basic, msg, 1);
+
+        // only proof-of-possession signatures can be aggregated
+        let ms = Builder::new(Codec::Bls12381G1Msig)
+            .add_signature(&[0u8], &build(&pop[0], msg))
+            .add_signature(&[1u8], &build(&basic[0], msg))
+            .try_build();
+        assert!(matches!(
+            ms,
+            Err(Error::Aggregate(AggregateError::ProofOfPossessionRequired))
+        ));
+
+        // each signer can only be added once
+        let ms = Builder::new(Codec::Bls12381G1Msig)
+            .add_signature(&[0u8], &build(&pop[0], msg))
+            .add_signature(&[0u8], &build(&pop[1], msg))
+            .try_build();
+        assert!(matches!(
+            ms,
+            Err(Error::Aggregate(AggregateError::DuplicateSigner))
+        ));
+
+        // every signer must sign the same message
+        let ms = Builder::new(Codec::Bls12381G1Msig)
+            .add_signature(&[0u8], &build(&pop[0], msg))
+            .add_signature(&[1u8], &build(&pop[1], b"move every zig!"))
+            .try_build();
+        assert!(matches!(
+            ms,
+            Err(Error::Aggregate(AggregateError::MessageMismatch))
+        ));
+    }
+
+    fn bls_message_signatures(
+        scheme: blsful::SignatureSchemes,
+        msgs: &[&[u8]],
+    ) -> Vec<(Signature<blsful::Bls12381G2Impl>, Multisig)> {
+        msgs.iter()
+            .map(|msg| {
+                let sk = blsful::Bls12381G2::new_secret_key();
+                let sig = sk.sign(scheme, msg).unwrap();
+                let ms = Builder::new_from_bls_signature(&sig)
+                    .unwrap()
+                    .with_message_bytes(msg)
+                    .try_build()
+                    .unwrap();
+                (sig, ms)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bls_signature_aggregate_messages() {
+        let msgs: [&[u8]; 3] = [b"block header 1", b"block header 2", b"block header 3"];
+        let sigs = bls_message_signatures(blsful::SignatureSchemes::Basic, &msgs);
+
+        let mut builder = Builder::new(Codec::Bls12381G1Msig);
+        for (i, (_, ms)) in sigs.iter().enumerate() {
+            builder = builder.add_message_signature(&[i as u8], ms);
+        }
+        let ms1 = builder.try_build().unwrap();
+        assert!(ms1.message.is_empty());
+
+        let av = ms1.aggregate_view().unwrap();
+        assert_eq!(3, av.signer_count().unwrap());
+        assert_eq!(
+            msgs.iter().map(|m| m.to_vec()).collect::<Vec<_>>(),
+            av.messages().unwrap()
+        );
+        let av = ms1.attr_view().unwrap();
+        assert_eq!(bls12381::SchemeTypeId::Basic.code(), av.scheme().unwrap());
+
+        // the aggregate signature is the sum of the signatures
+        let sum = sigs
+            .iter()
+            .map(|(sig, _)| *sig.as_raw_value())
+            .reduce(|a, b| a + b)
+            .unwrap();
+        let dv = ms1.data_view().unwrap();
+        assert_eq!(sum.to_bytes().as_ref().to_vec(), dv.sig_bytes().unwrap());
+
+        // the messages survive the round trip through SSH
+        let cv = ms1.conv_view().unwrap();
+        let ssh_ms = cv.to_ssh_signature().unwrap();
+        let ms2 = Builder::new_from_ssh_signature(&ssh_ms)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_bls_signature_aggregate_duplicate_messages() {
+        let msgs: [&[u8]; 2] = [b"block header 1", b"block header 1"];
+
+        // duplicate messages are refused without message augmentation
+        for scheme in [
+            blsful::SignatureSchemes::Basic,
+            blsful::SignatureSchemes::ProofOfPossession,
+        ] {
+            let sigs = bls_message_signatures(scheme, &msgs);
+            let ms = Builder::new(Codec::Bls12381G1Msig)
+                .add_message_signature(&[0u8], &sigs[0].1)
+                .add_message_signature(&[1u8], &sigs[1].1)
+                .try_build();
+            assert!(matches!(
+                ms,
+                Err(Error::Aggregate(AggregateError::DuplicateMessage))
+            ));
+        }
+
+        // message augmentation binds the message to the signer's key
+        let sigs = bls_message_signatures(blsful::SignatureSchemes::MessageAugmentation, &msgs);
+        let ms = Builder::new(Codec::Bls12381G1Msig)
+            .add_message_signature(&[0u8], &sigs[0].1)
+            .add_message_signature(&[1u8], &sigs[1].1)
+            .try_build()
+            .unwrap();
+        let av = ms.aggregate_view().unwrap();
+        assert_eq!(2, av.messages().unwrap().len());
+
+        // every signature must use the same scheme
+        let basic = bls_message_signatures(
+            blsful::SignatureSchemes::Basic,
+            &[b"block header 2".as_slice()],
+        );
+        assert!(matches!(
+            av.add_message_signature(&[2u8], &basic[0].1),
+            Err(Error::Aggregate(AggregateError::SchemeMismatch))
+        ));
+
+        // same-message and distinct-message aggregation can't be mixed
+        let pop = bls_message_signatures(
+            blsful::SignatureSchemes::ProofOfPossession,
+            &[b"block header 1".as_slice()],
+        );
+        assert!(matches!(
+            av.add_signature(&[2u8], &pop[0].1),
+            Err(Error::Aggregate(AggregateError::MixedAggregate))
+        ));
+    }
+
+    // split a Lamport signature into byte-wise Shamir shares using a fixed
+    // polynomial so the test is deterministic
+    fn lamport_shares(sig: &[u8], threshold: usize, limit: usize) -> Vec<Multisig> {
+        (1..=limit as u8)
+            .map(|x| {
+                let share: Vec<u8> = sig
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| {
+                        let mut y = *b;
+                        let mut xp = 1u8;
+                        for k in 1..threshold {
+                            xp = lamport::gf256_mul(xp, x);
+                            y ^= lamport::gf256_mul((i * 31 + k * 7) as u8, xp);
+                        }
+                        y
+                    })
+                    .collect();
+                Builder::new(Codec::LamportShareMsig)
+                    .with_scheme(lamport::SchemeTypeId::Sha3256.into())
+                    .with_identifier(x)
+                    .with_threshold(threshold)
+                    .with_limit(limit)
+                    .with_payload_encoding(Codec::Identity)
+                    .with_signature_bytes(&share)
+                    .try_build()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lamport() {
+        let scheme = lamport::SchemeTypeId::Sha3256;
+        let ms = Builder::new(Codec::LamportMsig)
+            .with_scheme(scheme.into())
+            .with_signature_bytes(&vec![0u8; scheme.sig_size()])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        let dv = ms.data_view().unwrap();
+        assert_eq!(8192, dv.sig_bytes().unwrap().len());
+    }
+
+    #[test]
+    fn test_slh_dsa_not_lamport() {
+        // SLH-DSA parameter sets are not Lamport schemes
+        assert!(lamport::SchemeTypeId::try_from("slh-dsa-sha2-128s").is_err());
+        assert!(lamport::SchemeTypeId::try_from(3u8).is_err());
+        let ms = Builder::new(Codec::LamportMsig)
+            .with_scheme(3)
+            .with_signature_bytes(&vec![7u8; 7856])
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+    }
+
+    fn bbs_messages() -> Vec<Vec<u8>> {
+        vec![
+            b"name: alice".to_vec(),
+            b"born: 1990-01-01".to_vec(),
+            b"country: nz".to_vec(),
+        ]
+    }
+
+    #[test]
+    fn test_bbs() {
+        let ms = Builder::new(Codec::Bls12381G1Msig)
+            .with_scheme(bbs::SchemeTypeId::BbsSha256.into())
+            .with_context(b"issuer header")
+            .with_message_vector(&bbs_messages())
+            .with_signature_bytes(&[5u8; bbs::SIGNATURE_SIZE])
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        let av = ms.attr_view().unwrap();
+        assert_eq!(bbs::SchemeTypeId::BbsSha256.code(), av.scheme().unwrap());
+        assert_eq!(b"issuer header".to_vec(), av.context().unwrap());
+        assert_eq!(
+            bbs::SIGNATURE_SIZE,
+            ms.data_view().unwrap().sig_bytes().unwrap().len()
+        );
+        let bv = ms.bbs_view().unwrap();
+        assert_eq!(bbs_messages(), bv.messages().unwrap());
+        assert!(bv.disclosed_indexes().is_err());
+
+        // the SSH form keeps the scheme and the header
+        let ms_ssh = ms.conv_view().unwrap().to_ssh_signature().unwrap();
+        assert_eq!(bbs::ALGORITHM_NAME, ms_ssh.algorithm().as_str());
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .with_message_vector(&bbs_messages())
+            .try_build()
+            .unwrap();
+        assert_eq!(ms, ms2);
+
+        // a BBS signature is not a BLS signature
+        let ms = Builder::new(Codec::Bls12381G1Msig)
+            .with_scheme(bbs::SchemeTypeId::BbsSha256.into())
+            .with_signature_bytes(&[5u8; 48])
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+    }
+
+    fn bbs_signature() -> Multisig {
+        Builder::new(Codec::Bls12381G1Msig)
+            .with_scheme(bbs::SchemeTypeId::BbsSha256.into())
+            .with_message_vector(&bbs_messages())
+            .with_signature_bytes(&[5u8; bbs::SIGNATURE_SIZE])
+            .try_build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bbs_threshold_attr_view_unsupported() {
+        assert!(matches!(
+            bbs_signature().threshold_attr_view(),
+            Err(Error::UnsupportedView(_))
+        ));
+    }
+
+    #[test]
+    fn test_bbs_threshold_view_unsupported() {
+        assert!(matches!(
+            bbs_signature().threshold_view(),
+            Err(Error::UnsupportedView(_))
+        ));
+    }
+
+    #[test]
+    fn test_bbs_aggregate_view_unsupported() {
+        assert!(matches!(
+            bbs_signature().aggregate_view(),
+            Err(Error::UnsupportedView(_))
+        ));
+    }
+
+    #[test]
+    fn test_bbs_proof() {
+        let disclosed = vec![bbs_messages()[0].clone(), bbs_messages()[2].clone()];
+        // one undisclosed message adds one scalar to the proof
+        let proof = vec![6u8; bbs::PROOF_MIN_SIZE + 32];
+        let ms = Builder::new(Codec::Bls12381G1Msig)
+            .with_scheme(bbs::SchemeTypeId::BbsProofShake256.into())
+            .with_context(b"issuer header")
+            .with_presentation_header(b"verifier nonce")
+            .with_disclosed_indexes(&[0, 2])
+            .with_message_vector(&disclosed)
+            .with_signature_bytes(&proof)
+            .try_build()
+            .unwrap();
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        assert_eq!(proof, ms.data_view().unwrap().sig_bytes().unwrap());
+        let bv = ms.bbs_view().unwrap();
+        assert_eq!(vec![0, 2], bv.disclosed_indexes().unwrap());
+        assert_eq!(disclosed, bv.messages().unwrap());
+        assert_eq!(
+            b"verifier nonce".to_vec(),
+            bv.presentation_header().unwrap()
+        );
+
+        // the SSH form keeps the headers and the disclosed indexes
+        let ms_ssh = ms.conv_view().unwrap().to_ssh_signature().unwrap();
+        assert_eq!(bbs::ALGORITHM_NAME_PROOF, ms_ssh.algorithm().as_str());
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .with_message_vector(&disclosed)
+            .try_build()
+            .unwrap();
+        assert_eq!(ms, ms2);
+    }
+
+    #[test]
+    fn test_bbs_proof_invalid() {
+        let proof = vec![6u8; bbs::PROOF_MIN_SIZE];
+        let builder = Builder::new(Codec::Bls12381G1Msig)
+            .with_scheme(bbs::SchemeTypeId::BbsProofSha256.into())
+            .with_message_vector(&bbs_messages()[..1]);
+
+        // the proof must be the fixed part plus whole scalars
+        let ms = builder
+            .clone()
+            .with_disclosed_indexes(&[1])
+            .with_signature_bytes(&[6u8; bbs::PROOF_MIN_SIZE + 1])
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+
+        // the disclosed indexes are required
+        let ms = builder
+            .clone()
+            .with_signature_bytes(&proof)
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+
+        // every disclosed index needs a disclosed message
+        let ms = builder
+            .clone()
+            .with_disclosed_indexes(&[0, 1])
+            .with_signature_bytes(&proof)
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+
+        // the disclosed indexes must be in ascending order
+        let ms = Builder::new(Codec::Bls12381G1Msig)
+            .with_scheme(bbs::SchemeTypeId::BbsProofSha256.into())
+            .with_disclosed_indexes(&[2, 0])
+            .with_signature_bytes(&proof)
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+    }
+
+    #[test]
+    fn test_messages_oversized_count() {
+        // a message count far larger than the encoded data fails to decode
+        // instead of pre-allocating the claimed number of messages
+        let bytes: Vec<u8> = Varuint(usize::MAX).into();
+        assert!(aggregate::Messages::try_from(bytes.as_slice()).is_err());
+    }
+
+    // build an XMSS-SHA2_h_256 shaped signature: the 4-byte leaf index, the
+    // randomizer, 67 one-time signature chains and the authentication path
+    fn xmss_signature(leaf_index: u32, tree_height: u8, fill: u8) -> Multisig {
+        let mut sig = leaf_index.to_be_bytes().to_vec();
+        sig.resize(4 + 32 * (68 + usize::from(tree_height)), fill);
+        Builder::new(Codec::LamportMsig)
+            .with_scheme(xmss::SchemeTypeId::XmssSha2256.into())
+            .with_leaf_index(u64::from(leaf_index))
+            .with_tree_height(tree_height)
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_xmss() {
+        let ms = xmss_signature(5, 10, 1);
+        let v: Vec<u8> = ms.clone().into();
+        assert_eq!(ms, Multisig::try_from(v.as_slice()).unwrap());
+        let sv = ms.stateful_view().unwrap();
+        assert_eq!(5, sv.leaf_index().unwrap());
+        assert_eq!(10, sv.tree_height().unwrap());
+        assert_eq!(2500, ms.data_view().unwrap().sig_bytes().unwrap().len());
+
+        // the leaf index must match the signature
+        let sig = ms.data_view().unwrap().sig_bytes().unwrap();
+        let ms = Builder::new(Codec::LamportMsig)
+            .with_scheme(xmss::SchemeTypeId::XmssSha2256.into())
+            .with_leaf_index(6)
+            .with_tree_height(10)
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+
+        // the leaf index must be inside the tree
+        let ms = xmss_signature(1024, 10, 1);
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+
+        // the signature size must match the tree height
+        let ms = Builder::new(Codec::LamportMsig)
+            .with_scheme(xmss::SchemeTypeId::XmssSha2256.into())
+            .with_leaf_index(5)
+            .with_tree_height(16)
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+    }
+
+    #[test]
+    fn test_xmss_not_lamport() {
+        let ms = xmss_signature(5, 10, 1);
+        let scheme = xmss::SchemeTypeId::XmssSha2256;
+
+        // stateful schemes are not Lamport schemes and have no threshold shares
+        assert!(lamport::SchemeTypeId::try_from(scheme.code()).is_err());
+        assert!(lamport::View::try_from(&ms).unwrap().sig_bytes().is_err());
+        assert!(ms.threshold_attr_view().is_err());
+        assert!(ms.threshold_view().is_err());
+        let share = Builder::new(Codec::LamportShareMsig)
+            .with_scheme(scheme.into())
+            .with_identifier(1)
+            .with_threshold(2)
+            .with_limit(3)
+            .with_signature_bytes(&ms.data_view().unwrap().sig_bytes().unwrap())
+            .try_build()
+            .unwrap();
+        assert!(share.data_view().unwrap().sig_bytes().is_err());
+    }
+
+    #[test]
+    fn test_lms() {
+        let mut sig = 0x1234u32.to_be_bytes().to_vec();
+        sig.resize(2828, 3);
+        let ms = Builder::new(Codec::LamportMsig)
+            .with_scheme(xmss::SchemeTypeId::LmsSha2256.into())
+            .with_leaf_index(0x1234)
+            .with_tree_height(20)
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        assert_eq!(sig, ms.data_view().unwrap().sig_bytes().unwrap());
+        assert_eq!(0x1234, ms.stateful_view().unwrap().leaf_index().unwrap());
+
+        let ms_ssh = ms.conv_view().unwrap().to_ssh_signature().unwrap();
+        assert_eq!(xmss::ALGORITHM_NAME, ms_ssh.algorithm().as_str());
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms, ms2);
+
+        // the leaf index and tree height are required
+        let ms = Builder::new(Codec::LamportMsig)
+            .with_scheme(xmss::SchemeTypeId::LmsSha2256.into())
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        assert!(ms.data_view().unwrap().sig_bytes().is_err());
+    }
+
+    #[test]
+    fn test_stateful_view_unsupported() {
+        let scheme = lamport::SchemeTypeId::Sha3256;
+        let ms = Builder::new(Codec::LamportMsig)
+            .with_scheme(scheme.into())
+            .with_signature_bytes(&vec![0u8; scheme.sig_size()])
+            .try_build()
+            .unwrap();
+        assert!(ms.stateful_view().is_err());
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        assert!(ms.stateful_view().is_err());
+    }
+
+    #[test]
+    fn test_leaf_index_reuse() {
+        let sigs = vec![
+            xmss_signature(1, 10, 1),
+            xmss_signature(2, 10, 1),
+            // the same signature seen twice is not reuse
+            xmss_signature(2, 10, 1),
+            xmss_signature(3, 10, 1),
+        ];
+        assert!(xmss::find_leaf_index_reuse(&sigs).unwrap().is_empty());
+
+        let sigs = vec![
+            xmss_signature(1, 10, 1),
+            xmss_signature(3, 10, 1),
+            xmss_signature(1, 10, 2),
+            xmss_signature(2, 10, 1),
+            xmss_signature(3, 10, 3),
+        ];
+        assert_eq!(vec![1, 3], xmss::find_leaf_index_reuse(&sigs).unwrap());
+
+        // only stateful signatures have leaf indexes
+        let scheme = lamport::SchemeTypeId::Sha3256;
+        let ms = Builder::new(Codec::LamportMsig)
+            .with_scheme(scheme.into())
+            .with_signature_bytes(&vec![0u8; scheme.sig_size()])
+            .try_build()
+            .unwrap();
+        assert!(xmss::find_leaf_index_reuse(&[ms]).is_err());
+    }
+
+    #[test]
+    fn test_lamport_signature_combine() {
+        let scheme = lamport::SchemeTypeId::Sha3256;
+        let sig: Vec<u8> = (0..scheme.sig_size()).map(|i| (i * 13) as u8).collect();
+        let ms1 = Builder::new(Codec::LamportMsig)
+            .with_scheme(scheme.into())
+            .with_payload_encoding(Codec::Identity)
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+
+        // build a new signature from the last three of the parts
+        let shares = lamport_shares(&sig, 3, 4);
+        let mut builder = Builder::new(Codec::LamportMsig);
+        for share in shares.iter().skip(1) {
+            builder = builder.add_signature_share(share);
+        }
+        let ms2 = builder.try_build().unwrap();
+
+        let av = ms2.threshold_attr_view().unwrap();
+        assert_eq!(3, av.threshold().unwrap());
+        assert_eq!(4, av.limit().unwrap());
+
+        let tv = ms2.threshold_view().unwrap();
+        assert_eq!(3, tv.shares().unwrap().len());
+        let ms3 = tv.combine().unwrap();
+
+        assert_eq!(ms1, ms3);
+    }
+
+    #[test]
+    fn test_lamport_not_enough_shares() {
+        let scheme = lamport::SchemeTypeId::Sha3256;
+        let sig = vec![0x42u8; scheme.sig_size()];
+        let shares = lamport_shares(&sig, 3, 4);
+        let ms = Builder::new(Codec::LamportMsig)
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let tv = ms.threshold_view().unwrap();
+        assert!(tv.combine().is_err());
+    }
+
+    #[test]
+    fn test_lamport_threshold_data_too_many_shares() {
+        use crate::error::SharesError;
+
+        // a share count above the signer limit
+        let bytes: Vec<u8> = Varuint(lamport::MAX_SIGNERS + 1).into();
+        assert!(matches!(
+            lamport::ThresholdData::try_from(bytes.as_slice()),
+            Err(Error::Shares(SharesError::TooManyShares))
+        ));
+    }
+
+    #[test]
+    fn test_lamport_share_ssh_roundtrip() {
+        let scheme = lamport::SchemeTypeId::Sha3256;
+        let sig = vec![0x42u8; scheme.sig_size()];
+        for ms1 in lamport_shares(&sig, 2, 3) {
+            let cv = ms1.conv_view().unwrap();
+            let ms_ssh = cv.to_ssh_signature().unwrap();
+            let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+                .unwrap()
+                .with_payload_encoding(Codec::Identity)
+                .try_build()
+                .unwrap();
+            assert_eq!(ms1, ms2);
+        }
+    }
+
+    #[test]
+    fn test_eddsa_ssh_roundtrip() {
+        let ms1 = Builder::new(Codec::EddsaMsig)
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_ed448_ssh_roundtrip() {
+        for context in [b"".to_vec(), b"for great justice".to_vec()] {
+            let mut builder = Builder::new(Codec::EddsaMsig)
+                .with_scheme(eddsa::SchemeTypeId::Ed448.into())
+                .with_signature_bytes(&[0x1du8; 114]);
+            if !context.is_empty() {
+                builder = builder.with_context(&context);
+            }
+            let ms1 = builder.try_build().unwrap();
+            let cv = ms1.conv_view().unwrap();
+            let ms_ssh = cv.to_ssh_signature().unwrap();
+            assert_eq!(ed448::ALGORITHM_NAME, ms_ssh.algorithm().as_str());
+            let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+                .unwrap()
+                .try_build()
+                .unwrap();
+            assert_eq!(ms1, ms2);
+        }
+    }
+
+    #[test]
+    fn test_ed448_ssh_ed448_roundtrip() {
+        use ssh_encoding::Encode;
+
+        let ms1 = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Ed448.into())
+            .with_signature_bytes(&[0x1du8; 114])
+            .try_build()
+            .unwrap();
+        let blob = ed448::to_ssh_ed448_signature(&ms1).unwrap();
+        assert_eq!(&[0, 0, 0, 9], &blob[..4]);
+        assert_eq!(ed448::SSH_ALGORITHM_NAME.as_bytes(), &blob[4..13]);
+        assert_eq!(4 + 9 + 4 + 114, blob.len());
+        let ms2 = Builder::new_from_ssh_ed448_signature(&blob)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+
+        // ssh-ed448 signatures have no context
+        let ms3 = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Ed448.into())
+            .with_context(b"for great justice")
+            .with_signature_bytes(&[0x1du8; 114])
+            .try_build()
+            .unwrap();
+        assert!(ed448::to_ssh_ed448_signature(&ms3).is_err());
+
+        // the ed448@multisig name is accepted on import too
+        let mut blob: Vec<u8> = Vec::default();
+        ed448::ALGORITHM_NAME.encode(&mut blob).unwrap();
+        let sig_data: Vec<u8> =
+            ed448::SigContext(b"for great justice".to_vec(), [0x1du8; 114].to_vec()).into();
+        sig_data.encode(&mut blob).unwrap();
+        let ms4 = Builder::new_from_ssh_ed448_signature(&blob)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms3, ms4);
+
+        // only Ed448 signatures convert
+        let ms5 = Builder::new(Codec::EddsaMsig)
+            .with_signature_bytes(&[0x1du8; 64])
+            .try_build()
+            .unwrap();
+        assert!(ed448::to_ssh_ed448_signature(&ms5).is_err());
+    }
+
+    #[test]
+    fn test_ed25519_variants_ssh_roundtrip() {
+        for (scheme, name) in [
+            (eddsa::SchemeTypeId::Ed25519ph, ed25519::ALGORITHM_NAME_PH),
+            (eddsa::SchemeTypeId::Ed25519ctx, ed25519::ALGORITHM_NAME_CTX),
+        ] {
+            let ms1 = Builder::new(Codec::EddsaMsig)
+                .with_scheme(scheme.into())
+                .with_context(b"for great justice")
+                .with_signature_bytes(&[0x1du8; 64])
+                .try_build()
+                .unwrap();
+            let cv = ms1.conv_view().unwrap();
+            let ms_ssh = cv.to_ssh_signature().unwrap();
+            assert_eq!(name, ms_ssh.algorithm().as_str());
+            let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+                .unwrap()
+                .try_build()
+                .unwrap();
+            assert_eq!(ms1, ms2);
+        }
+    }
+
+    #[test]
+    fn test_sr25519_ssh_roundtrip() {
+        let mut sig = [0x2au8; 64];
+        sig[63] |= 0x80;
+        let ms1 = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::Sr25519.into())
+            .with_context(b"substrate")
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(sr25519::ALGORITHM_NAME, ms_ssh.algorithm().as_str());
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_xeddsa_ssh_roundtrip() {
+        let ms1 = Builder::new(Codec::EddsaMsig)
+            .with_scheme(eddsa::SchemeTypeId::XEdDsa.into())
+            .with_signature_bytes(&[0x1du8; 64])
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(xeddsa::ALGORITHM_NAME, ms_ssh.algorithm().as_str());
+
+        // the SSH signature states the key type to verify against
+        let sig_key_type = xeddsa::SigKeyType::try_from(ms_ssh.as_bytes()).unwrap();
+        assert_eq!(Codec::X25519Pub, sig_key_type.0);
+
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_ed25519_half_aggregate_ssh_roundtrip() {
+        let (keys, sigs) = ed25519_signatures(3);
+        let ms1 = Builder::new_from_ed25519_signatures(&sigs, &keys)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(ed25519_halfagg::ALGORITHM_NAME, ms_ssh.algorithm().as_str());
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_es256k_ssh_roundtrip() {
+        let ms1 = Builder::new(Codec::Es256KMsig)
+            .with_signature_bytes(&[0u8; 64])
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_es256k_schnorr_ssh_roundtrip() {
+        let ms1 = Builder::new(Codec::Es256KMsig)
+            .with_scheme(secp256k1::SchemeTypeId::Schnorr.into())
+            .with_signature_bytes(&[0x7fu8; 64])
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(
+            secp256k1::ALGORITHM_NAME_SCHNORR,
+            ms_ssh.algorithm().as_str()
+        );
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_es256k_recoverable_ssh_roundtrip() {
+        let (_, mut rsv, _) = secp256k1_recoverable(b"for great justice");
+        rsv[64] += 37;
+        let ms1 = Builder::new_from_secp256k1_rsv(&rsv)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(
+            secp256k1::ALGORITHM_NAME_RECOVERABLE,
+            ms_ssh.algorithm().as_str()
+        );
+        assert_eq!(rsv.as_slice(), ms_ssh.as_bytes());
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_es256_ssh_roundtrip() {
+        // r has its high bit set and s has leading zeros to exercise the
+        // mpint padding in both directions
+        let mut sig = [0x80u8; 64];
+        sig[32] = 0;
+        sig[33] = 0;
+        let ms1 = Builder::new(Codec::Es256Msig)
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(
+            ssh_key::Algorithm::Ecdsa {
+                curve: ssh_key::EcdsaCurve::NistP256
+            },
+            ms_ssh.algorithm()
+        );
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_es256_invalid_length() {
+        let ms = Builder::new(Codec::Es256Msig)
+            .with_signature_bytes(&[1u8; 63])
+            .try_build()
+            .unwrap();
+        let dv = ms.data_view().unwrap();
+        assert!(dv.sig_bytes().is_err());
+    }
+
+    #[test]
+    fn test_es384_ssh_roundtrip() {
+        let mut sig = [0xa5u8; 96];
+        sig[48] = 0;
+        let ms1 = Builder::new(Codec::Es384Msig)
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(
+            ssh_key::Algorithm::Ecdsa {
+                curve: ssh_key::EcdsaCurve::NistP384
+            },
+            ms_ssh.algorithm()
+        );
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_es521_ssh_roundtrip() {
+        let mut sig = [0xa5u8; 132];
+        sig[0] = 1;
+        sig[66] = 0;
+        sig[67] = 0;
+        let ms1 = Builder::new(Codec::Es521Msig)
+            .with_signature_bytes(&sig)
+            .try_build()
+            .unwrap();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(
+            ssh_key::Algorithm::Ecdsa {
+                curve: ssh_key::EcdsaCurve::NistP521
+            },
+            ms_ssh.algorithm()
+        );
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_es521_invalid_component() {
+        let ms = Builder::new(Codec::Es521Msig)
+            .with_signature_bytes(&[0xffu8; 132])
+            .try_build()
+            .unwrap();
+        let dv = ms.data_view().unwrap();
+        assert!(dv.sig_bytes().is_err());
+    }
+
+    #[test]
+    fn test_rs256_ssh_roundtrip() {
+        for (scheme, hash) in [
+            (rsa::SchemeTypeId::Pkcs1v15Sha256, ssh_key::HashAlg::Sha256),
+            (rsa::SchemeTypeId::Pkcs1v15Sha512, ssh_key::HashAlg::Sha512),
+        ] {
+            let ms1 = Builder::new(Codec::Rs256Msig)
+                .with_scheme(scheme.into())
+                .with_modulus_size(384)
+                .with_signature_bytes(&[0x5au8; 384])
+                .try_build()
+                .unwrap();
+            let cv = ms1.conv_view().unwrap();
+            let ms_ssh = cv.to_ssh_signature().unwrap();
+            assert_eq!(
+                ssh_key::Algorithm::Rsa { hash: Some(hash) },
+                ms_ssh.algorithm()
+            );
+            let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+                .unwrap()
+                .try_build()
+                .unwrap();
+            assert_eq!(ms1, ms2);
+        }
+    }
+
+    #[test]
+    fn test_rs256_pss_no_ssh() {
+        let ms = Builder::new(Codec::Rs256Msig)
+            .with_scheme(rsa::SchemeTypeId::PssSha512.into())
+            .with_modulus_size(512)
+            .with_signature_bytes(&[0x5au8; 512])
+            .try_build()
+            .unwrap();
+        let cv = ms.conv_view().unwrap();
+        assert!(cv.to_ssh_signature().is_err());
+    }
+
+    #[test]
+    fn test_bls_signature_ssh_roundtrip() {
+        let sk = blsful::Bls12381G1::new_secret_key();
+        let sig = sk
+            .sign(
+                blsful::SignatureSchemes::ProofOfPossession,
+                b"for great justice, move every zig!",
+            )
+            .unwrap();
+
+        let ms1 = Builder::new_from_bls_signature(&sig)
+            .unwrap()
+            .try_build()
+            .unwrap();
+
+        let cv = ms1.conv_view().unwrap();
+        let ssh_ms = cv.to_ssh_signature().unwrap();
+
+        let ms2 = Builder::new_from_ssh_signature(&ssh_ms)
+            .unwrap()
+            .try_build()
+            .unwrap();
+
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_bls_signature_aggregate_ssh_roundtrip() {
+        let msg = b"for great justice, move every zig!";
+        let mut builder = Builder::new(Codec::Bls12381G1Msig);
+        for (i, sig) in bls_signatures(blsful::SignatureSchemes::ProofOfPossession, msg, 2)
+            .iter()
+            .enumerate()
+        {
+            let ms = Builder::new_from_bls_signature(sig)
+                .unwrap()
+                .try_build()
+                .unwrap();
+            builder = builder.add_signature(&[i as u8], &ms);
+        }
+        let ms1 = builder.try_build().unwrap();
+
+        let cv = ms1.conv_view().unwrap();
+        let ssh_ms = cv.to_ssh_signature().unwrap();
+        assert_eq!(
+            bls12381::ALGORITHM_NAME_G1_AGGREGATE,
+            ssh_ms.algorithm().as_str()
+        );
+
+        let ms2 = Builder::new_from_ssh_signature(&ssh_ms)
+            .unwrap()
+            .try_build()
+            .unwrap();
+
+        assert_eq!(ms1, ms2);
+    }
+
+    #[test]
+    fn test_bls_signature_combine_ssh_roundtrip() {
+        let sk = blsful::Bls12381G2::new_secret_key();
+        let sig = sk
+            .sign(
+                blsful::SignatureSchemes::ProofOfPossession,
+                b"for great justice, move every zig!",
+            )
+            .unwrap();
+
+        let ms1 = Builder::new_from_bls_signature(&sig)
+            .unwrap()
+            .try_build()
+            .unwrap();
+
+        let sk_shares = sk.split(3, 4).unwrap();
 
-        let sk_shares = sk.split(3, 4).unwrap();
-
         let mut sigs = Vec::default();
         sk_shares.iter().for_each(|sk| {
             let sig = sk
@@ -714,6 +3244,936 @@ mod tests {
         assert_eq!(ms1, ms3);
     }
 
+    fn frost_ed25519_shares(msg: &[u8], signers: &[u8]) -> (Vec<u8>, Vec<Multisig>) {
+        use curve25519_dalek::{EdwardsPoint, Scalar};
+        use sha2::{Digest, Sha512};
+
+        // deterministic test scalars
+        let scalar = |label: &str, i: u8| {
+            let mut hasher = Sha512::new();
+            hasher.update(label.as_bytes());
+            hasher.update([i]);
+            Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+        };
+
+        // 2-of-3 Shamir sharing of the group secret key
+        let secret = scalar("secret", 0);
+        let coefficient = scalar("coefficient", 1);
+        let group_key = EdwardsPoint::mul_base(&secret);
+
+        // round one: each signer commits to a hiding and a binding nonce
+        let nonces: Vec<(Scalar, Scalar)> = signers
+            .iter()
+            .map(|i| (scalar("hiding", *i), scalar("binding", *i)))
+            .collect();
+        let package = frost::SigningPackage(
+            group_key.compress().as_bytes().to_vec(),
+            signers
+                .iter()
+                .zip(&nonces)
+                .map(|(i, (d, e))| {
+                    frost::Commitment(
+                        *i,
+                        EdwardsPoint::mul_base(d).compress().as_bytes().to_vec(),
+                        EdwardsPoint::mul_base(e).compress().as_bytes().to_vec(),
+                    )
+                })
+                .collect(),
+        );
+
+        // round two: each signer computes their signature share
+        let rhos = frost::ed25519_binding_factors(&package, msg);
+        let r = frost::ed25519_group_commitment(&package, msg).unwrap();
+        let c = frost::ed25519_challenge(&r, &group_key, msg);
+        let tdata: Vec<u8> = package.into();
+        let shares = signers
+            .iter()
+            .zip(&nonces)
+            .zip(&rhos)
+            .map(|((i, (d, e)), rho)| {
+                let x = Scalar::from(*i);
+                let lambda = signers
+                    .iter()
+                    .filter(|j| *j != i)
+                    .fold(Scalar::ONE, |acc, j| {
+                        acc * Scalar::from(*j) * (Scalar::from(*j) - x).invert()
+                    });
+                let z = d + e * rho + lambda * (secret + coefficient * x) * c;
+                Builder::new(Codec::EddsaMsig)
+                    .with_scheme(eddsa::SchemeTypeId::FrostShare.into())
+                    .with_identifier(*i)
+                    .with_threshold(2)
+                    .with_limit(3)
+                    .with_payload_encoding(Codec::Identity)
+                    .with_signature_bytes(z.as_bytes())
+                    .with_threshold_data(&tdata)
+                    .try_build()
+                    .unwrap()
+            })
+            .collect();
+
+        (group_key.compress().as_bytes().to_vec(), shares)
+    }
+
+    #[test]
+    fn test_frost_ed25519_combine() {
+        let msg = b"for great justice, move every zig!";
+        let (group_key, shares) = frost_ed25519_shares(msg, &[1, 3]);
+
+        let ms1 = Builder::new(Codec::EddsaMsig)
+            .with_message_bytes(msg)
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let tv = ms1.threshold_view().unwrap();
+        assert_eq!(2, tv.shares().unwrap().len());
+
+        let ms2 = tv.combine().unwrap();
+        assert_eq!(Codec::EddsaMsig, ms2.codec());
+        let av = ms2.attr_view().unwrap();
+        assert_eq!(eddsa::SchemeTypeId::Ed25519.code(), av.scheme().unwrap());
+
+        // any Ed25519 verifier accepts the aggregate signature
+        let dv = ms2.data_view().unwrap();
+        let sig = ed25519_dalek::Signature::from_slice(&dv.sig_bytes().unwrap()).unwrap();
+        let vk = ed25519_dalek::VerifyingKey::from_bytes(&group_key.try_into().unwrap()).unwrap();
+        assert!(vk.verify_strict(msg, &sig).is_ok());
+    }
+
+    #[test]
+    fn test_frost_ed25519_not_enough_shares() {
+        let msg = b"for great justice, move every zig!";
+        let (_, shares) = frost_ed25519_shares(msg, &[1, 3]);
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_message_bytes(msg)
+            .add_signature_share(&shares[0])
+            .try_build()
+            .unwrap();
+        let tv = ms.threshold_view().unwrap();
+        assert!(tv.combine().is_err());
+    }
+
+    #[test]
+    fn test_frost_ed25519_bad_share() {
+        let msg = b"for great justice, move every zig!";
+        let (_, mut shares) = frost_ed25519_shares(msg, &[1, 2, 3]);
+        shares[1]
+            .attributes
+            .insert(AttrId::SigData, [1u8; frost::ED25519_SHARE_SIZE].to_vec());
+        let mut builder = Builder::new(Codec::EddsaMsig).with_message_bytes(msg);
+        for share in &shares {
+            builder = builder.add_signature_share(share);
+        }
+        let ms = builder.try_build().unwrap();
+        let tv = ms.threshold_view().unwrap();
+        assert!(tv.combine().is_err());
+    }
+
+    #[test]
+    fn test_frost_ed25519_package_mismatch() {
+        let msg = b"for great justice, move every zig!";
+        let (_, shares1) = frost_ed25519_shares(msg, &[1, 2]);
+        let (_, shares2) = frost_ed25519_shares(msg, &[1, 3]);
+        let ms = Builder::new(Codec::EddsaMsig)
+            .with_message_bytes(msg)
+            .add_signature_share(&shares1[0])
+            .add_signature_share(&shares2[1])
+            .try_build();
+        assert!(ms.is_err());
+    }
+
+    #[test]
+    fn test_frost_ed25519_share_ssh_roundtrip() {
+        let (_, shares) = frost_ed25519_shares(b"move every zig!", &[1, 3]);
+        let ms1 = shares[1].clone();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(
+            frost::ALGORITHM_NAME_ED25519_SHARE,
+            ms_ssh.algorithm().as_str()
+        );
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .with_payload_encoding(Codec::Identity)
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    fn frost_secp256k1_shares(
+        msg: &[u8],
+        signers: &[u8],
+        taproot: bool,
+    ) -> (Vec<u8>, Vec<Multisig>) {
+        use k256::{
+            elliptic_curve::{group::GroupEncoding as _, ops::Reduce, point::AffineCoordinates},
+            ProjectivePoint, Scalar, U256,
+        };
+        use sha2::{Digest, Sha256};
+
+        // deterministic test scalars
+        let scalar = |label: &str, i: u8| {
+            let mut hasher = Sha256::new();
+            hasher.update(label.as_bytes());
+            hasher.update([i]);
+            <Scalar as Reduce<U256>>::reduce_bytes(&hasher.finalize())
+        };
+        let (cs, scheme) = if taproot {
+            (
+                frost::Ciphersuite::Secp256k1Tr,
+                secp256k1::SchemeTypeId::FrostTaprootShare,
+            )
+        } else {
+            (
+                frost::Ciphersuite::Secp256k1,
+                secp256k1::SchemeTypeId::FrostShare,
+            )
+        };
+
+        // 2-of-3 Shamir sharing of the group secret key
+        let secret = scalar("secret", 0);
+        let coefficient = scalar("coefficient", 1);
+        let group_key = ProjectivePoint::GENERATOR * secret;
+
+        // round one: each signer commits to a hiding and a binding nonce
+        let nonces: Vec<(Scalar, Scalar)> = signers
+            .iter()
+            .map(|i| (scalar("hiding", *i), scalar("binding", *i)))
+            .collect();
+        let package = frost::SigningPackage(
+            group_key.to_affine().to_bytes().to_vec(),
+            signers
+                .iter()
+                .zip(&nonces)
+                .map(|(i, (d, e))| {
+                    frost::Commitment(
+                        *i,
+                        (ProjectivePoint::GENERATOR * d)
+                            .to_affine()
+                            .to_bytes()
+                            .to_vec(),
+                        (ProjectivePoint::GENERATOR * e)
+                            .to_affine()
+                            .to_bytes()
+                            .to_vec(),
+                    )
+                })
+                .collect(),
+        );
+
+        // round two: each signer computes their signature share. BIP-340
+        // signers negate their nonces and key shares when R or the group key
+        // has an odd y coordinate.
+        let rhos = frost::secp256k1_binding_factors(cs, &package, msg);
+        let r = frost::secp256k1_group_commitment(cs, &package, msg).unwrap();
+        let c = frost::secp256k1_challenge(cs, &r, &group_key, msg);
+        let parity = |p: ProjectivePoint| {
+            if taproot && bool::from(p.to_affine().y_is_odd()) {
+                -Scalar::ONE
+            } else {
+                Scalar::ONE
+            }
+        };
+        let (nonce_sign, key_sign) = (parity(r), parity(group_key));
+        let tdata: Vec<u8> = package.into();
+        let shares = signers
+            .iter()
+            .zip(&nonces)
+            .zip(&rhos)
+            .map(|((i, (d, e)), rho)| {
+                let x = Scalar::from(*i as u64);
+                let lambda = signers
+                    .iter()
+                    .filter(|j| *j != i)
+                    .fold(Scalar::ONE, |acc, j| {
+                        let xj = Scalar::from(*j as u64);
+                        acc * xj * (xj - x).invert().unwrap()
+                    });
+                let z =
+                    nonce_sign * (d + e * rho) + lambda * key_sign * (secret + coefficient * x) * c;
+                Builder::new(Codec::Es256KMsig)
+                    .with_scheme(scheme.into())
+                    .with_identifier(*i)
+                    .with_threshold(2)
+                    .with_limit(3)
+                    .with_payload_encoding(Codec::Identity)
+                    .with_signature_bytes(&z.to_bytes())
+                    .with_threshold_data(&tdata)
+                    .try_build()
+                    .unwrap()
+            })
+            .collect();
+
+        (group_key.to_affine().to_bytes().to_vec(), shares)
+    }
+
+    #[test]
+    fn test_frost_secp256k1_combine() {
+        let msg = b"for great justice, move every zig!";
+        let (_, shares) = frost_secp256k1_shares(msg, &[1, 3], false);
+
+        let ms1 = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(msg)
+            .with_scheme(secp256k1::SchemeTypeId::Frost.into())
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let tv = ms1.threshold_view().unwrap();
+        let ms2 = tv.combine().unwrap();
+        assert_eq!(Codec::Es256KMsig, ms2.codec());
+        let av = ms2.attr_view().unwrap();
+        assert_eq!(secp256k1::SchemeTypeId::Frost.code(), av.scheme().unwrap());
+        let dv = ms2.data_view().unwrap();
+        assert_eq!(
+            secp256k1::FROST_SIGNATURE_SIZE,
+            dv.sig_bytes().unwrap().len()
+        );
+    }
+
+    fn unhex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_frost_ed25519_rfc9591_vectors() {
+        // RFC 9591 Appendix E.1, FROST(Ed25519, SHA-512)
+        let msg = unhex("74657374");
+        let group_key = "15d21ccd7ee42959562fc8aa63224c8851fb3ec85a3faf66040d380fb9738673";
+        let package = frost::SigningPackage(
+            unhex(group_key),
+            vec![
+                frost::Commitment(
+                    1,
+                    unhex("b5aa8ab305882a6fc69cbee9327e5a45e54c08af61ae77cb8207be3d2ce13de3"),
+                    unhex("67e98ab55aa310c3120418e5050c9cf76cf387cb20ac9e4b6fdb6f82a469f932"),
+                ),
+                frost::Commitment(
+                    3,
+                    unhex("cfbdb165bd8aad6eb79deb8d287bcc0ab6658ae57fdcc98ed12c0669e90aec91"),
+                    unhex("7487bc41a6e712eea2f2af24681b58b1cf1da278ea11fe4e8b78398965f13552"),
+                ),
+            ],
+        );
+        let sig = unhex(
+            "36282629c383bb820a88b71cae937d41f2f2adfcc3d02e55507e2fb9e2dd3cbe\
+             bd9d2b0844e49ae0f3fa935161e1419aab7b47d21a37ebeae1f17d4987b3160b",
+        );
+
+        // binding factors
+        let rhos = frost::ed25519_binding_factors(&package, &msg);
+        assert_eq!(
+            unhex("f2cb9d7dd9beff688da6fcc83fa89046b3479417f47f55600b106760eb3b5603"),
+            rhos[0].as_bytes()
+        );
+        assert_eq!(
+            unhex("b087686bf35a13f3dc78e780a34b0fe8a77fef1b9938c563f5573d71d8d7890f"),
+            rhos[1].as_bytes()
+        );
+
+        // group commitment
+        let r = frost::ed25519_group_commitment(&package, &msg).unwrap();
+        assert_eq!(&sig[..32], r.compress().as_bytes());
+
+        // final signature
+        let tdata: Vec<u8> = package.into();
+        let shares: Vec<Multisig> = [
+            (
+                1,
+                "001719ab5a53ee1a12095cd088fd149702c0720ce5fd2f29dbecf24b7281b603",
+            ),
+            (
+                3,
+                "bd86125de990acc5e1f13781d8e32c03a9bbd4c53539bbc106058bfd14326007",
+            ),
+        ]
+        .iter()
+        .map(|(i, z)| {
+            Builder::new(Codec::EddsaMsig)
+                .with_scheme(eddsa::SchemeTypeId::FrostShare.into())
+                .with_identifier(*i)
+                .with_threshold(2)
+                .with_limit(3)
+                .with_payload_encoding(Codec::Identity)
+                .with_signature_bytes(&unhex(z))
+                .with_threshold_data(&tdata)
+                .try_build()
+                .unwrap()
+        })
+        .collect();
+        let ms1 = Builder::new(Codec::EddsaMsig)
+            .with_message_bytes(&msg)
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let ms2 = ms1.threshold_view().unwrap().combine().unwrap();
+        assert_eq!(sig, ms2.data_view().unwrap().sig_bytes().unwrap());
+    }
+
+    #[test]
+    fn test_frost_secp256k1_rfc9591_vectors() {
+        use k256::elliptic_curve::group::GroupEncoding as _;
+
+        // RFC 9591 Appendix E.5, FROST(secp256k1, SHA-256)
+        let cs = frost::Ciphersuite::Secp256k1;
+        let msg = unhex("74657374");
+        let group_key = "02f37c34b66ced1fb51c34a90bdae006901f10625cc06c4f64663b0eae87d87b4f";
+        let package = frost::SigningPackage(
+            unhex(group_key),
+            vec![
+                frost::Commitment(
+                    1,
+                    unhex("03c699af97d26bb4d3f05232ec5e1938c12f1e6ae97643c8f8f11c9820303f1904"),
+                    unhex("02fa2aaccd51b948c9dc1a325d77226e98a5a3fe65fe9ba213761a60123040a45e"),
+                ),
+                frost::Commitment(
+                    3,
+                    unhex("03077507ba327fc074d2793955ef3410ee3f03b82b4cdc2370f71d865beb926ef6"),
+                    unhex("02ad53031ddfbbacfc5fbda3d3b0c2445c8e3e99cbc4ca2db2aa283fa68525b135"),
+                ),
+            ],
+        );
+        let sig = unhex(
+            "0205b6d04d3774c8929413e3c76024d54149c372d57aae62574ed74319b5ea14d0\
+             c65dde8492a7471437e6c2fe3da49b90d23f642b5c6dbe7e36089f096dd97324",
+        );
+
+        // binding factors
+        let rhos = frost::secp256k1_binding_factors(cs, &package, &msg);
+        assert_eq!(
+            unhex("3e08fe561e075c653cbfd46908a10e7637c70c74f0a77d5fd45d1a750c739ec6"),
+            rhos[0].to_bytes().to_vec()
+        );
+        assert_eq!(
+            unhex("93f79041bb3fd266105be251adaeb5fd7f8b104fb554a4ba9a0becea48ddbfd7"),
+            rhos[1].to_bytes().to_vec()
+        );
+
+        // group commitment
+        let r = frost::secp256k1_group_commitment(cs, &package, &msg).unwrap();
+        assert_eq!(&sig[..33], r.to_affine().to_bytes().as_slice());
+
+        // final signature
+        let tdata: Vec<u8> = package.into();
+        let shares: Vec<Multisig> = [
+            (
+                1,
+                "c4fce1775a1e141fb579944166eab0d65eefe7b98d480a569bbbfcb14f91c197",
+            ),
+            (
+                3,
+                "0160fd0d388932f4826d2ebcd6b9eaba734f7c71cf25b4279a4ca2581e47b18d",
+            ),
+        ]
+        .iter()
+        .map(|(i, z)| {
+            Builder::new(Codec::Es256KMsig)
+                .with_scheme(secp256k1::SchemeTypeId::FrostShare.into())
+                .with_identifier(*i)
+                .with_threshold(2)
+                .with_limit(3)
+                .with_payload_encoding(Codec::Identity)
+                .with_signature_bytes(&unhex(z))
+                .with_threshold_data(&tdata)
+                .try_build()
+                .unwrap()
+        })
+        .collect();
+        let ms1 = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(&msg)
+            .with_scheme(secp256k1::SchemeTypeId::Frost.into())
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let ms2 = ms1.threshold_view().unwrap().combine().unwrap();
+        assert_eq!(sig, ms2.data_view().unwrap().sig_bytes().unwrap());
+    }
+
+    #[test]
+    fn test_frost_signing_package_too_many_commitments() {
+        use crate::error::SharesError;
+
+        // a group key followed by a commitment count above the signer limit
+        let mut bytes: Vec<u8> = Varbytes(vec![0u8; 32]).into();
+        bytes.append(&mut Varuint(frost::MAX_SIGNERS + 1).into());
+        assert!(matches!(
+            frost::SigningPackage::try_from(bytes.as_slice()),
+            Err(Error::Shares(SharesError::TooManyShares))
+        ));
+    }
+
+    #[test]
+    fn test_frost_threshold_data_too_many_shares() {
+        use crate::error::SharesError;
+
+        // a signing package followed by a share count above the signer limit
+        let mut bytes: Vec<u8> = frost::SigningPackage(vec![0u8; 32], Vec::default()).into();
+        bytes.append(&mut Varuint(frost::MAX_SIGNERS + 1).into());
+        assert!(matches!(
+            frost::ThresholdData::try_from(bytes.as_slice()),
+            Err(Error::Shares(SharesError::TooManyShares))
+        ));
+    }
+
+    #[test]
+    fn test_frost_secp256k1_taproot_combine() {
+        let msg = b"for great justice, move every zig!";
+        let (group_key, shares) = frost_secp256k1_shares(msg, &[2, 3], true);
+
+        let ms1 = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(msg)
+            .with_scheme(secp256k1::SchemeTypeId::Schnorr.into())
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let tv = ms1.threshold_view().unwrap();
+        let ms2 = tv.combine().unwrap();
+        let av = ms2.attr_view().unwrap();
+        assert_eq!(
+            secp256k1::SchemeTypeId::Schnorr.code(),
+            av.scheme().unwrap()
+        );
+
+        // any BIP-340 verifier accepts the aggregate signature
+        let dv = ms2.data_view().unwrap();
+        let sig = k256::schnorr::Signature::try_from(dv.sig_bytes().unwrap().as_slice()).unwrap();
+        let vk = k256::schnorr::VerifyingKey::from_bytes(&group_key[1..]).unwrap();
+        assert!(vk.verify_raw(msg, &sig).is_ok());
+    }
+
+    #[test]
+    fn test_frost_secp256k1_ciphersuite_mismatch() {
+        let msg = b"for great justice, move every zig!";
+        let (_, shares) = frost_secp256k1_shares(msg, &[1, 3], true);
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(msg)
+            .with_scheme(secp256k1::SchemeTypeId::Frost.into())
+            .add_signature_share(&shares[0])
+            .try_build();
+        assert!(ms.is_err());
+    }
+
+    #[test]
+    fn test_frost_secp256k1_share_ssh_roundtrip() {
+        for taproot in [false, true] {
+            let (_, shares) = frost_secp256k1_shares(b"move every zig!", &[1, 3], taproot);
+            let ms1 = shares[0].clone();
+            let cv = ms1.conv_view().unwrap();
+            let ms_ssh = cv.to_ssh_signature().unwrap();
+            let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+                .unwrap()
+                .with_payload_encoding(Codec::Identity)
+                .try_build()
+                .unwrap();
+            assert_eq!(ms1, ms2);
+        }
+    }
+
+    fn musig2_partials(msg: &[u8], n: u8) -> (Vec<u8>, Vec<Multisig>) {
+        use k256::{
+            elliptic_curve::{group::GroupEncoding as _, ops::Reduce, point::AffineCoordinates},
+            ProjectivePoint, Scalar, U256,
+        };
+        use sha2::{Digest, Sha256};
+
+        // deterministic test scalars
+        let scalar = |label: &str, i: u8| {
+            let mut hasher = Sha256::new();
+            hasher.update(label.as_bytes());
+            hasher.update([i]);
+            <Scalar as Reduce<U256>>::reduce_bytes(&hasher.finalize())
+        };
+        let compressed = |s: &Scalar| {
+            (ProjectivePoint::GENERATOR * s)
+                .to_affine()
+                .to_bytes()
+                .to_vec()
+        };
+
+        // each signer has a key pair and a pair of secret nonces
+        let secrets: Vec<Scalar> = (1..=n).map(|i| scalar("secret", i)).collect();
+        let nonces: Vec<(Scalar, Scalar)> = (1..=n)
+            .map(|i| (scalar("nonce1", i), scalar("nonce2", i)))
+            .collect();
+        let keys = musig2::KeyList(secrets.iter().map(compressed).collect());
+        let pubnonces: Vec<Vec<u8>> = nonces
+            .iter()
+            .map(|(k1, k2)| [compressed(k1), compressed(k2)].concat())
+            .collect();
+
+        // each signer computes their partial signature
+        let (q, coefficients) = musig2::key_agg(&keys).unwrap();
+        let nonce_refs: Vec<&[u8]> = pubnonces.iter().map(|v| v.as_slice()).collect();
+        let (b, r, e) = musig2::session_values(&q, &nonce_refs, msg).unwrap();
+        let nonce_sign = if musig2::has_even_y(&r) {
+            Scalar::ONE
+        } else {
+            -Scalar::ONE
+        };
+        let key_sign = if musig2::has_even_y(&q) {
+            Scalar::ONE
+        } else {
+            -Scalar::ONE
+        };
+        let tdata: Vec<u8> = keys.into();
+        let partials = secrets
+            .iter()
+            .zip(&nonces)
+            .zip(&coefficients)
+            .zip(&pubnonces)
+            .enumerate()
+            .map(|(i, (((x, (k1, k2)), a), pubnonce))| {
+                let s = nonce_sign * (k1 + b * k2) + e * a * key_sign * x;
+                Builder::new(Codec::Es256KMsig)
+                    .with_scheme(secp256k1::SchemeTypeId::Musig2Partial.into())
+                    .with_identifier(i as u8 + 1)
+                    .with_threshold(n as usize)
+                    .with_limit(n as usize)
+                    .with_payload_encoding(Codec::Identity)
+                    .with_public_nonce(pubnonce)
+                    .with_signature_bytes(&s.to_bytes())
+                    .with_threshold_data(&tdata)
+                    .try_build()
+                    .unwrap()
+            })
+            .collect();
+
+        (q.to_affine().x().to_vec(), partials)
+    }
+
+    #[test]
+    fn test_musig2_combine() {
+        let msg = b"for great justice, move every zig!";
+        let (agg_key, partials) = musig2_partials(msg, 3);
+
+        let mut builder = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(msg)
+            .with_scheme(secp256k1::SchemeTypeId::Musig2.into());
+        for partial in &partials {
+            builder = builder.add_signature_share(partial);
+        }
+        let ms1 = builder.try_build().unwrap();
+        let av = ms1.threshold_attr_view().unwrap();
+        assert_eq!(3, av.threshold().unwrap());
+        assert_eq!(3, av.limit().unwrap());
+        let tv = ms1.threshold_view().unwrap();
+        assert_eq!(3, tv.shares().unwrap().len());
+
+        let ms2 = tv.combine().unwrap();
+        let av = ms2.attr_view().unwrap();
+        assert_eq!(
+            secp256k1::SchemeTypeId::Schnorr.code(),
+            av.scheme().unwrap()
+        );
+
+        // any BIP-340 verifier accepts the aggregate signature
+        let dv = ms2.data_view().unwrap();
+        let sig = k256::schnorr::Signature::try_from(dv.sig_bytes().unwrap().as_slice()).unwrap();
+        let vk = k256::schnorr::VerifyingKey::from_bytes(&agg_key).unwrap();
+        assert!(vk.verify_raw(msg, &sig).is_ok());
+    }
+
+    #[test]
+    fn test_musig2_missing_partial() {
+        let msg = b"for great justice, move every zig!";
+        let (_, partials) = musig2_partials(msg, 3);
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(msg)
+            .with_scheme(secp256k1::SchemeTypeId::Musig2.into())
+            .add_signature_share(&partials[0])
+            .add_signature_share(&partials[2])
+            .try_build()
+            .unwrap();
+        let tv = ms.threshold_view().unwrap();
+        assert!(tv.combine().is_err());
+    }
+
+    #[test]
+    fn test_musig2_bad_partial() {
+        let msg = b"for great justice, move every zig!";
+        let (_, mut partials) = musig2_partials(msg, 2);
+        partials[1]
+            .attributes
+            .insert(AttrId::SigData, [1u8; musig2::PARTIAL_SIG_SIZE].to_vec());
+        let ms = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(msg)
+            .with_scheme(secp256k1::SchemeTypeId::Musig2.into())
+            .add_signature_share(&partials[0])
+            .add_signature_share(&partials[1])
+            .try_build()
+            .unwrap();
+        let tv = ms.threshold_view().unwrap();
+        assert!(tv.combine().is_err());
+    }
+
+    #[test]
+    fn test_musig2_threshold_data_too_many_shares() {
+        use crate::error::SharesError;
+
+        // a key list followed by a partial signature count above the signer
+        // limit
+        let mut bytes: Vec<u8> = musig2::KeyList(Vec::default()).into();
+        bytes.append(&mut Varuint(musig2::MAX_SIGNERS + 1).into());
+        assert!(matches!(
+            musig2::ThresholdData::try_from(bytes.as_slice()),
+            Err(Error::Shares(SharesError::TooManyShares))
+        ));
+    }
+
+    #[test]
+    fn test_musig2_partial_ssh_roundtrip() {
+        let (_, partials) = musig2_partials(b"move every zig!", 2);
+        let ms1 = partials[1].clone();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(musig2::ALGORITHM_NAME_PARTIAL, ms_ssh.algorithm().as_str());
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .with_payload_encoding(Codec::Identity)
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
+    fn threshold_ecdsa_shares<C>(
+        codec: Codec,
+        scheme: u8,
+        msg: &[u8],
+        signers: &[u8],
+    ) -> (Vec<u8>, Vec<Multisig>)
+    where
+        C: elliptic_curve::CurveArithmetic
+            + elliptic_curve::Curve<FieldBytesSize = elliptic_curve::consts::U32>,
+        C::AffinePoint: elliptic_curve::sec1::ToEncodedPoint<C>,
+    {
+        use elliptic_curve::{
+            group::{Curve as _, Group},
+            ops::Reduce,
+            point::AffineCoordinates,
+            sec1::ToEncodedPoint,
+            Field, PrimeField,
+        };
+        use sha2::{Digest, Sha256};
+
+        // deterministic test scalars
+        let scalar = |label: &str, i: u8| {
+            let mut hasher = Sha256::new();
+            hasher.update(label.as_bytes());
+            hasher.update([i]);
+            <C::Scalar as Reduce<C::Uint>>::reduce_bytes(&hasher.finalize())
+        };
+        let encode =
+            |p: C::ProjectivePoint| p.to_affine().to_encoded_point(true).as_bytes().to_vec();
+
+        // the presignature point is the inverse of the nonce times the
+        // generator
+        let g = C::ProjectivePoint::generator();
+        let secret = scalar("secret", 0);
+        let nonce = scalar("nonce", 0);
+        let r_point = g * nonce.invert().unwrap();
+        let r = <C::Scalar as Reduce<C::Uint>>::reduce_bytes(&r_point.to_affine().x());
+        let m = <C::Scalar as Reduce<C::Uint>>::reduce_bytes(&Sha256::digest(msg));
+
+        // the signers hold additive shares of the nonce and of the nonce
+        // times the secret key
+        let split = |label: &str, total: C::Scalar| {
+            let mut parts: Vec<C::Scalar> =
+                signers[1..].iter().map(|i| scalar(label, *i)).collect();
+            let sum = parts.iter().fold(C::Scalar::ZERO, |acc, p| acc + p);
+            parts.insert(0, total - sum);
+            parts
+        };
+        let nonces = split("nonce share", nonce);
+        let masked = split("masked key share", nonce * secret);
+        let presig = threshold_ecdsa::Presignature(
+            encode(g * secret),
+            encode(r_point),
+            signers
+                .iter()
+                .zip(&nonces)
+                .zip(&masked)
+                .map(|((i, k), chi)| {
+                    threshold_ecdsa::PresignatureShare(
+                        *i,
+                        encode(r_point * *k),
+                        encode(r_point * *chi),
+                    )
+                })
+                .collect(),
+        );
+        let tdata: Vec<u8> = presig.into();
+
+        // each signer computes their signature share
+        let shares = signers
+            .iter()
+            .zip(&nonces)
+            .zip(&masked)
+            .map(|((i, k), chi)| {
+                let sigma = *k * m + r * *chi;
+                Builder::new(codec)
+                    .with_scheme(scheme)
+                    .with_identifier(*i)
+                    .with_threshold(2)
+                    .with_limit(3)
+                    .with_payload_encoding(Codec::Identity)
+                    .with_signature_bytes(&sigma.to_repr())
+                    .with_threshold_data(&tdata)
+                    .try_build()
+                    .unwrap()
+            })
+            .collect();
+
+        (encode(g * secret), shares)
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_secp256k1_combine() {
+        use k256::ecdsa::signature::Verifier;
+
+        let msg = b"for great justice, move every zig!";
+        let (vk, shares) = threshold_ecdsa_shares::<k256::Secp256k1>(
+            Codec::Es256KMsig,
+            secp256k1::SchemeTypeId::EcdsaShare.code(),
+            msg,
+            &[1, 3],
+        );
+
+        let ms1 = Builder::new(Codec::Es256KMsig)
+            .with_message_bytes(msg)
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let tv = ms1.threshold_view().unwrap();
+        assert_eq!(2, tv.shares().unwrap().len());
+
+        let ms2 = tv.combine().unwrap();
+        assert_eq!(Codec::Es256KMsig, ms2.codec());
+        let av = ms2.attr_view().unwrap();
+        assert_eq!(secp256k1::SchemeTypeId::Ecdsa.code(), av.scheme().unwrap());
+
+        // the combined signature is a plain low-S ECDSA signature
+        let dv = ms2.data_view().unwrap();
+        let sig = k256::ecdsa::Signature::from_slice(&dv.sig_bytes().unwrap()).unwrap();
+        assert!(sig.normalize_s().is_none());
+        let vk = k256::ecdsa::VerifyingKey::from_sec1_bytes(&vk).unwrap();
+        assert!(vk.verify(msg, &sig).is_ok());
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_p256_combine() {
+        use ::p256::ecdsa::signature::Verifier;
+
+        let msg = b"for great justice, move every zig!";
+        let (vk, shares) = threshold_ecdsa_shares::<::p256::NistP256>(
+            Codec::Es256Msig,
+            p256::SchemeTypeId::EcdsaShare.code(),
+            msg,
+            &[2, 3],
+        );
+
+        let ms1 = Builder::new(Codec::Es256Msig)
+            .with_message_bytes(msg)
+            .add_signature_share(&shares[0])
+            .add_signature_share(&shares[1])
+            .try_build()
+            .unwrap();
+        let tv = ms1.threshold_view().unwrap();
+        let ms2 = tv.combine().unwrap();
+        assert_eq!(Codec::Es256Msig, ms2.codec());
+
+        let dv = ms2.data_view().unwrap();
+        let sig = ::p256::ecdsa::Signature::from_slice(&dv.sig_bytes().unwrap()).unwrap();
+        assert!(sig.normalize_s().is_none());
+        let vk = ::p256::ecdsa::VerifyingKey::from_sec1_bytes(&vk).unwrap();
+        assert!(vk.verify(msg, &sig).is_ok());
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_identifiable_abort() {
+        use crate::error::SharesError;
+
+        let msg = b"for great justice, move every zig!";
+        let (_, mut shares) = threshold_ecdsa_shares::<k256::Secp256k1>(
+            Codec::Es256KMsig,
+            secp256k1::SchemeTypeId::EcdsaShare.code(),
+            msg,
+            &[1, 2, 3],
+        );
+        shares[1]
+            .attributes
+            .insert(AttrId::SigData, [1u8; threshold_ecdsa::SHARE_SIZE].to_vec());
+        let mut builder = Builder::new(Codec::Es256KMsig).with_message_bytes(msg);
+        for share in &shares {
+            builder = builder.add_signature_share(share);
+        }
+        let ms = builder.try_build().unwrap();
+        let tv = ms.threshold_view().unwrap();
+        assert!(matches!(
+            tv.combine(),
+            Err(Error::Shares(SharesError::IdentifiableAbort(2)))
+        ));
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_threshold_data_too_many_shares() {
+        use crate::error::SharesError;
+
+        // a presignature followed by a share count above the signer limit
+        let mut bytes: Vec<u8> =
+            threshold_ecdsa::Presignature(vec![0u8; 33], vec![0u8; 33], Vec::default()).into();
+        bytes.append(&mut Varuint(threshold_ecdsa::MAX_SIGNERS + 1).into());
+        assert!(matches!(
+            threshold_ecdsa::ThresholdData::try_from(bytes.as_slice()),
+            Err(Error::Shares(SharesError::TooManyShares))
+        ));
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_presignature_too_many_shares() {
+        use crate::error::SharesError;
+
+        // a verifying key and presignature point followed by a share count
+        // above the signer limit
+        let mut bytes: Vec<u8> = Varbytes(vec![0u8; 33]).into();
+        bytes.append(&mut Varbytes(vec![0u8; 33]).into());
+        bytes.append(&mut Varuint(threshold_ecdsa::MAX_SIGNERS + 1).into());
+        assert!(matches!(
+            threshold_ecdsa::Presignature::try_from(bytes.as_slice()),
+            Err(Error::Shares(SharesError::TooManyShares))
+        ));
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_share_ssh_roundtrip() {
+        let (_, shares) = threshold_ecdsa_shares::<::p256::NistP256>(
+            Codec::Es256Msig,
+            p256::SchemeTypeId::EcdsaShare.code(),
+            b"move every zig!",
+            &[1, 3],
+        );
+        let ms1 = shares[1].clone();
+        let cv = ms1.conv_view().unwrap();
+        let ms_ssh = cv.to_ssh_signature().unwrap();
+        assert_eq!(
+            threshold_ecdsa::ALGORITHM_NAME_P256_SHARE,
+            ms_ssh.algorithm().as_str()
+        );
+        let ms2 = Builder::new_from_ssh_signature(&ms_ssh)
+            .unwrap()
+            .with_payload_encoding(Codec::Identity)
+            .try_build()
+            .unwrap();
+        assert_eq!(ms1, ms2);
+    }
+
     #[test]
     fn test_null() {
         let ms1 = Multisig::null();
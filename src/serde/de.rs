@@ -60,6 +60,9 @@ impl<'de> Deserialize<'de> for AttrId {
 }
 
 /// Deserialize instance of [`crate::Multisig`]
+///
+/// Malleable high-S ECDSA signatures are not rejected, use
+/// [`crate::Multisig::try_decode_strict`] for that.
 impl<'de> Deserialize<'de> for Multisig {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1,13 +1,47 @@
 // SPDX-License-Idnetifier: Apache-2.0
-use crate::{Error, Multisig};
+use crate::{error::AttributesError, views::ecdsa::SignatureEncoding, Error, Multisig};
 use multicodec::Codec;
 
+/// Signer and message lists shared by aggregate signatures
+pub mod aggregate;
+/// BBS signature and selective disclosure proof implementation
+pub mod bbs;
 /// BLS12 381 G1/G2 signature implementation
 pub mod bls12381;
+/// ECDSA signature encodings shared by the ECDSA codecs
+pub mod ecdsa;
 /// Edwards curve 25519 signature implementation
 pub mod ed25519;
+/// Edwards curve 25519 signature half-aggregation
+pub mod ed25519_halfagg;
+/// Edwards curve 448 signature implementation
+pub mod ed448;
+/// EdDSA signature scheme identifiers
+pub mod eddsa;
+/// FROST threshold Schnorr signature implementation
+pub mod frost;
+/// Lamport one-time signature implementation
+pub mod lamport;
+/// MuSig2 multi-party Schnorr signature implementation
+pub mod musig2;
+/// NIST P-256 curve ECDSA signature implementation
+pub mod p256;
+/// NIST P-384 curve ECDSA signature implementation
+pub mod p384;
+/// NIST P-521 curve ECDSA signature implementation
+pub mod p521;
+/// RSA PKCS#1 v1.5 and PSS signature implementation
+pub mod rsa;
 /// Koblitz 256k1 curve implmentation (a.k.a. the Bitcoin curve)
 pub mod secp256k1;
+/// Schnorrkel sr25519 signature implementation
+pub mod sr25519;
+/// Threshold ECDSA signature implementation
+pub mod threshold_ecdsa;
+/// XEdDSA signatures made with X25519 keys
+pub mod xeddsa;
+/// XMSS and LMS stateful hash-based signature implementation
+pub mod xmss;
 
 /// trait for returning the attributes of the Multisig
 pub trait AttrView {
@@ -15,6 +49,11 @@ pub trait AttrView {
     fn payload_encoding(&self) -> Result<Codec, Error>;
     /// get the signing scheme identifier if any
     fn scheme(&self) -> Result<u8, Error>;
+    /// get the signing context string, empty if the scheme takes a context
+    /// and none was given
+    fn context(&self) -> Result<Vec<u8>, Error> {
+        Err(AttributesError::MissingContext.into())
+    }
 }
 
 /// trait for returning the data from a Multisig
@@ -39,6 +78,10 @@ pub trait ThresholdAttrView {
     fn identifier(&self) -> Result<u8, Error>;
     /// get the threshold data associated with the signature
     fn threshold_data(&self) -> Result<&[u8], Error>;
+    /// get the public nonce for this multisig share
+    fn public_nonce(&self) -> Result<&[u8], Error> {
+        Err(AttributesError::MissingPublicNonce.into())
+    }
 }
 
 /// trait for accumulating shares to rebuild a threshold signature
@@ -51,6 +94,69 @@ pub trait ThresholdView {
     fn combine(&self) -> Result<Multisig, Error>;
 }
 
+/// trait for aggregating signatures made by distinct signers
+pub trait AggregateView {
+    /// get the number of signers in the aggregate signature
+    fn signer_count(&self) -> Result<usize, Error>;
+    /// get the key identifiers of the signers in the order they were added
+    fn signer_key_ids(&self) -> Result<Vec<Vec<u8>>, Error>;
+    /// get the messages signed by each signer in the order they were added
+    fn messages(&self) -> Result<Vec<Vec<u8>>, Error>;
+    /// add a signature made by the signer with the given key identifier over
+    /// the same message as the others and return the Multisig with the
+    /// signature aggregated in
+    fn add_signature(&self, key_id: &[u8], sig: &Multisig) -> Result<Multisig, Error>;
+    /// add a signature made by the signer with the given key identifier over
+    /// its own message and return the Multisig with the signature and the
+    /// message aggregated in
+    fn add_message_signature(&self, key_id: &[u8], sig: &Multisig) -> Result<Multisig, Error>;
+}
+
+/// trait for recoverable signatures
+pub trait RecoveryView {
+    /// get the recovery id of the signature
+    fn recovery_id(&self) -> Result<u8, Error>;
+    /// get the EIP-155 chain id the signature was made for
+    fn chain_id(&self) -> Result<u64, Error>;
+    /// get the 65-byte r||s||v form of the signature, with v adjusted for
+    /// the chain id if there is one
+    fn to_rsv(&self) -> Result<Vec<u8>, Error>;
+    /// recover the SEC1 compressed public key of the signer from the message
+    /// digest
+    fn recover_public_key(&self, digest: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+/// trait for converting between ECDSA signature encodings
+pub trait EcdsaView {
+    /// get the encoding the signature arrived in
+    fn signature_encoding(&self) -> Result<SignatureEncoding, Error>;
+    /// get the ASN.1 DER SEQUENCE { r, s } encoding of the signature
+    fn to_der(&self) -> Result<Vec<u8>, Error>;
+    /// check if s is in the lower half of the group order. fails if r or s is
+    /// zero or not less than the group order.
+    fn is_low_s(&self) -> Result<bool, Error>;
+    /// get a canonical low-S copy of the Multisig
+    fn normalize_s(&self) -> Result<Multisig, Error>;
+}
+
+/// trait for stateful hash-based signatures
+pub trait StatefulView {
+    /// get the one-time leaf index the signature was made with
+    fn leaf_index(&self) -> Result<u64, Error>;
+    /// get the total height of the tree, or hypertree, of one-time leaves
+    fn tree_height(&self) -> Result<u8, Error>;
+}
+
+/// trait for BBS signatures and selective disclosure proofs
+pub trait BbsView {
+    /// get the signed messages, or the messages disclosed by a proof
+    fn messages(&self) -> Result<Vec<Vec<u8>>, Error>;
+    /// get the indexes of the messages disclosed by a proof
+    fn disclosed_indexes(&self) -> Result<Vec<usize>, Error>;
+    /// get the presentation header of a proof
+    fn presentation_header(&self) -> Result<Vec<u8>, Error>;
+}
+
 /// trait for getting the other views
 pub trait Views {
     /// Provide a read-only view to access the signature attributes
@@ -63,4 +169,24 @@ pub trait Views {
     fn threshold_attr_view<'a>(&'a self) -> Result<Box<dyn ThresholdAttrView + 'a>, Error>;
     /// Provide the view for adding a share to a multisig
     fn threshold_view<'a>(&'a self) -> Result<Box<dyn ThresholdView + 'a>, Error>;
+    /// Provide the view for aggregating signatures into a multisig
+    fn aggregate_view<'a>(&'a self) -> Result<Box<dyn AggregateView + 'a>, Error> {
+        Err(Error::UnsupportedView("aggregate".to_string()))
+    }
+    /// Provide the view for recovering the signer of a recoverable signature
+    fn recovery_view<'a>(&'a self) -> Result<Box<dyn RecoveryView + 'a>, Error> {
+        Err(Error::UnsupportedView("recovery".to_string()))
+    }
+    /// Provide the view for converting between ECDSA signature encodings
+    fn ecdsa_view<'a>(&'a self) -> Result<Box<dyn EcdsaView + 'a>, Error> {
+        Err(Error::UnsupportedView("ecdsa".to_string()))
+    }
+    /// Provide the view for the leaf index of a stateful hash-based signature
+    fn stateful_view<'a>(&'a self) -> Result<Box<dyn StatefulView + 'a>, Error> {
+        Err(Error::UnsupportedView("stateful".to_string()))
+    }
+    /// Provide the view for BBS signatures and selective disclosure proofs
+    fn bbs_view<'a>(&'a self) -> Result<Box<dyn BbsView + 'a>, Error> {
+        Err(Error::UnsupportedView("bbs".to_string()))
+    }
 }
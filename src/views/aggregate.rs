@@ -0,0 +1,94 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::Error;
+use multitrait::TryDecodeFrom;
+use multiutil::{Varbytes, Varuint};
+
+/// the key identifiers of the signers of an aggregate signature, in the order
+/// their signatures were added
+#[derive(Clone, Default, PartialEq)]
+pub struct SignerKeyIds(pub Vec<Vec<u8>>);
+
+impl From<SignerKeyIds> for Vec<u8> {
+    fn from(val: SignerKeyIds) -> Self {
+        let mut v = Vec::default();
+        // add in the number of signers
+        v.append(&mut Varuint(val.0.len()).into());
+        // add in the key identifiers
+        val.0.iter().for_each(|key_id| {
+            v.append(&mut Varbytes(key_id.clone()).into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SignerKeyIds {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (key_ids, _) = Self::try_decode_from(bytes)?;
+        Ok(key_ids)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SignerKeyIds {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the number of signers
+        let (num_signers, ptr) = Varuint::<usize>::try_decode_from(bytes)?;
+        // try to decode the key identifiers
+        let mut key_ids = Vec::new();
+        let mut p = ptr;
+        for _ in 0..*num_signers {
+            let (key_id, ptr) = Varbytes::try_decode_from(p)?;
+            key_ids.push(key_id.to_inner());
+            p = ptr;
+        }
+        Ok((Self(key_ids), p))
+    }
+}
+
+/// the messages, or message digests, signed by each signer of an aggregate
+/// signature over distinct messages, in the order their signatures were added
+#[derive(Clone, Default, PartialEq)]
+pub struct Messages(pub Vec<Vec<u8>>);
+
+impl From<Messages> for Vec<u8> {
+    fn from(val: Messages) -> Self {
+        let mut v = Vec::default();
+        // add in the number of messages
+        v.append(&mut Varuint(val.0.len()).into());
+        // add in the messages
+        val.0.iter().for_each(|msg| {
+            v.append(&mut Varbytes(msg.clone()).into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Messages {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (messages, _) = Self::try_decode_from(bytes)?;
+        Ok(messages)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for Messages {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the number of messages
+        let (num_messages, ptr) = Varuint::<usize>::try_decode_from(bytes)?;
+        // try to decode the messages
+        let mut messages = Vec::new();
+        let mut p = ptr;
+        for _ in 0..*num_messages {
+            let (msg, ptr) = Varbytes::try_decode_from(p)?;
+            messages.push(msg.to_inner());
+            p = ptr;
+        }
+        Ok((Self(messages), p))
+    }
+}
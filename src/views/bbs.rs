@@ -0,0 +1,474 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    views::aggregate::Messages,
+    AttrId, AttrView, BbsView, ConvView, DataView, Error, Multisig, Views,
+};
+use multicodec::Codec;
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::{Varbytes, Varuint};
+use std::fmt;
+
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME: &str = "bbs@multisig";
+/// the name used to identify these proofs in non-Multikey formats
+pub const ALGORITHM_NAME_PROOF: &str = "bbs-proof@multisig";
+
+/// the size of a BLS12-381 compressed G1 point
+const POINT_SIZE: usize = 48;
+/// the size of a BLS12-381 scalar
+const SCALAR_SIZE: usize = 32;
+
+/// the size of a BBS signature, the G1 point A followed by the scalar e
+pub const SIGNATURE_SIZE: usize = POINT_SIZE + SCALAR_SIZE;
+
+/// the size of a BBS proof that discloses every message: the G1 points Abar,
+/// Bbar and D followed by the scalars e^, r1^, r3^ and the challenge. each
+/// undisclosed message adds one more scalar before the challenge.
+pub const PROOF_MIN_SIZE: usize = 3 * POINT_SIZE + 4 * SCALAR_SIZE;
+
+/// The BBS signature and proof schemes carried by the Bls12381G1Msig codec,
+/// one for each ciphersuite of the [IRTF CFRG BBS draft][1]. Their codes
+/// follow the BLS signature scheme codes so the scheme attribute tells BBS
+/// and BLS signatures apart.
+///
+/// [1]: https://datatracker.ietf.org/doc/draft-irtf-cfrg-bbs-signatures/
+#[repr(u8)]
+#[derive(Clone, Copy, Hash, Ord, PartialOrd, PartialEq, Eq)]
+pub enum SchemeTypeId {
+    /// BBS signature, BLS12-381-SHA-256 ciphersuite
+    BbsSha256 = 3,
+    /// BBS signature, BLS12-381-SHAKE-256 ciphersuite
+    BbsShake256,
+    /// BBS selective disclosure proof, BLS12-381-SHA-256 ciphersuite
+    BbsProofSha256,
+    /// BBS selective disclosure proof, BLS12-381-SHAKE-256 ciphersuite
+    BbsProofShake256,
+}
+
+impl SchemeTypeId {
+    /// Get the code for the scheme
+    pub fn code(&self) -> u8 {
+        (*self).into()
+    }
+
+    /// Convert the scheme to &str
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::BbsSha256 => "bbs-sha-256",
+            Self::BbsShake256 => "bbs-shake-256",
+            Self::BbsProofSha256 => "bbs-proof-sha-256",
+            Self::BbsProofShake256 => "bbs-proof-shake-256",
+        }
+    }
+
+    /// Check if the scheme is for a selective disclosure proof
+    pub fn is_proof(&self) -> bool {
+        matches!(self, Self::BbsProofSha256 | Self::BbsProofShake256)
+    }
+}
+
+impl From<SchemeTypeId> for u8 {
+    fn from(val: SchemeTypeId) -> Self {
+        val as u8
+    }
+}
+
+impl TryFrom<u8> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            3 => Ok(Self::BbsSha256),
+            4 => Ok(Self::BbsShake256),
+            5 => Ok(Self::BbsProofSha256),
+            6 => Ok(Self::BbsProofShake256),
+            _ => Err(AttributesError::InvalidScheme(c).into()),
+        }
+    }
+}
+
+impl From<SchemeTypeId> for Vec<u8> {
+    fn from(val: SchemeTypeId) -> Self {
+        val.code().encode_into()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (id, _) = Self::try_decode_from(bytes)?;
+        Ok(id)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (code, ptr) = u8::try_decode_from(bytes)?;
+        Ok((Self::try_from(code)?, ptr))
+    }
+}
+
+impl TryFrom<&str> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "bbs-sha-256" => Ok(Self::BbsSha256),
+            "bbs-shake-256" => Ok(Self::BbsShake256),
+            "bbs-proof-sha-256" => Ok(Self::BbsProofSha256),
+            "bbs-proof-shake-256" => Ok(Self::BbsProofShake256),
+            _ => Err(AttributesError::InvalidSchemeName(s.to_string()).into()),
+        }
+    }
+}
+
+impl TryFrom<&Multisig> for SchemeTypeId {
+    type Error = Error;
+
+    /// get the BBS scheme from the Multisig. BBS signatures always record
+    /// their scheme.
+    fn try_from(ms: &Multisig) -> Result<Self, Self::Error> {
+        let v = ms
+            .attributes
+            .get(&AttrId::Scheme)
+            .ok_or(AttributesError::MissingScheme)?;
+        Self::try_from(Varuint::<u8>::try_from(v.as_slice())?.to_inner())
+    }
+}
+
+impl fmt::Display for SchemeTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// check if a Multisig holds a BBS signature or proof rather than a BLS
+/// signature
+pub(crate) fn is_bbs(ms: &Multisig) -> bool {
+    ms.codec == Codec::Bls12381G1Msig && SchemeTypeId::try_from(ms).is_ok()
+}
+
+/// the indexes of the messages disclosed by a BBS proof
+#[derive(Clone, Default, PartialEq)]
+pub struct DisclosedIndexes(pub Vec<usize>);
+
+impl From<DisclosedIndexes> for Vec<u8> {
+    fn from(val: DisclosedIndexes) -> Self {
+        let mut v = Vec::default();
+        // add in the number of indexes
+        v.append(&mut Varuint(val.0.len()).into());
+        // add in the indexes
+        val.0.iter().for_each(|i| {
+            v.append(&mut Varuint(*i).into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for DisclosedIndexes {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (indexes, _) = Self::try_decode_from(bytes)?;
+        Ok(indexes)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for DisclosedIndexes {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the number of indexes
+        let (num_indexes, ptr) = Varuint::<usize>::try_decode_from(bytes)?;
+        // try to decode the indexes
+        let mut indexes = Vec::new();
+        let mut p = ptr;
+        for _ in 0..*num_indexes {
+            let (i, ptr) = Varuint::<usize>::try_decode_from(p)?;
+            indexes.push(i.to_inner());
+            p = ptr;
+        }
+        Ok((Self(indexes), p))
+    }
+}
+
+/// tuple of BBS signature data
+#[derive(Clone)]
+pub struct SigBbs(
+    /// signature scheme
+    pub SchemeTypeId,
+    /// header
+    pub Vec<u8>,
+    /// signature bytes
+    pub Vec<u8>,
+);
+
+impl From<SigBbs> for Vec<u8> {
+    fn from(val: SigBbs) -> Self {
+        let mut v = Vec::default();
+        // add in the signature type id
+        v.append(&mut val.0.into());
+        // add in the header
+        v.append(&mut Varbytes(val.1.clone()).into());
+        // add in the signature bytes
+        v.append(&mut Varbytes(val.2.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigBbs {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (sig, _) = Self::try_decode_from(bytes)?;
+        Ok(sig)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigBbs {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signature type
+        let (sig_type, ptr) = SchemeTypeId::try_decode_from(bytes)?;
+        if sig_type.is_proof() {
+            return Err(AttributesError::InvalidScheme(sig_type.code()).into());
+        }
+        // try to decode the header
+        let (header, ptr) = Varbytes::try_decode_from(ptr)?;
+        // try to decode the signature bytes
+        let (sig_data, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((Self(sig_type, header.to_inner(), sig_data.to_inner()), ptr))
+    }
+}
+
+/// tuple of BBS proof data
+#[derive(Clone)]
+pub struct SigProof(
+    /// proof scheme
+    pub SchemeTypeId,
+    /// header
+    pub Vec<u8>,
+    /// presentation header
+    pub Vec<u8>,
+    /// disclosed message indexes
+    pub DisclosedIndexes,
+    /// proof bytes
+    pub Vec<u8>,
+);
+
+impl From<SigProof> for Vec<u8> {
+    fn from(val: SigProof) -> Self {
+        let mut v = Vec::default();
+        // add in the proof type id
+        v.append(&mut val.0.into());
+        // add in the header
+        v.append(&mut Varbytes(val.1.clone()).into());
+        // add in the presentation header
+        v.append(&mut Varbytes(val.2.clone()).into());
+        // add in the disclosed indexes
+        v.append(&mut val.3.into());
+        // add in the proof bytes
+        v.append(&mut Varbytes(val.4.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigProof {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (proof, _) = Self::try_decode_from(bytes)?;
+        Ok(proof)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigProof {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the proof type
+        let (proof_type, ptr) = SchemeTypeId::try_decode_from(bytes)?;
+        if !proof_type.is_proof() {
+            return Err(AttributesError::InvalidScheme(proof_type.code()).into());
+        }
+        // try to decode the header
+        let (header, ptr) = Varbytes::try_decode_from(ptr)?;
+        // try to decode the presentation header
+        let (presentation_header, ptr) = Varbytes::try_decode_from(ptr)?;
+        // try to decode the disclosed indexes
+        let (indexes, ptr) = DisclosedIndexes::try_decode_from(ptr)?;
+        // try to decode the proof bytes
+        let (proof, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((
+            Self(
+                proof_type,
+                header.to_inner(),
+                presentation_header.to_inner(),
+                indexes,
+                proof.to_inner(),
+            ),
+            ptr,
+        ))
+    }
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+    scheme: SchemeTypeId,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        let scheme = SchemeTypeId::try_from(ms)?;
+        Ok(Self { ms, scheme })
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for BBS Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// BBS signatures and proofs record the ciphersuite in the scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(self.scheme.code())
+    }
+    /// the BBS header is stored using the AttrId::Context attribute id. it is
+    /// empty if none was given.
+    fn context(&self) -> Result<Vec<u8>, Error> {
+        Ok(self
+            .ms
+            .attributes
+            .get(&AttrId::Context)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+impl DataView for View<'_> {
+    /// For BBS Multisig values, the signature or proof is stored using the
+    /// AttrId::SigData attribute id.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        if self.scheme.is_proof() {
+            if sig.len() < PROOF_MIN_SIZE || (sig.len() - PROOF_MIN_SIZE) % SCALAR_SIZE != 0 {
+                return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+            }
+            // make sure the disclosed indexes and messages agree
+            self.disclosed_indexes()?;
+            self.messages()?;
+        } else if sig.len() != SIGNATURE_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+        let header = self.context()?;
+
+        let (name, sig_data) = if self.scheme.is_proof() {
+            let indexes = DisclosedIndexes(self.disclosed_indexes()?);
+            let sig_data: Vec<u8> = SigProof(
+                self.scheme,
+                header,
+                self.presentation_header()?,
+                indexes,
+                sig_bytes,
+            )
+            .into();
+            (ALGORITHM_NAME_PROOF, sig_data)
+        } else {
+            let sig_data: Vec<u8> = SigBbs(self.scheme, header, sig_bytes).into();
+            (ALGORITHM_NAME, sig_data)
+        };
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(name).map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
+
+impl BbsView for View<'_> {
+    /// the message vector is stored in the message field as a count followed
+    /// by each message. a proof only carries the disclosed messages, in the
+    /// order of the disclosed indexes.
+    fn messages(&self) -> Result<Vec<Vec<u8>>, Error> {
+        if self.ms.message.is_empty() {
+            return Ok(Vec::default());
+        }
+        let messages = Messages::try_from(self.ms.message.as_slice())?.0;
+        if self.scheme.is_proof() {
+            let indexes = self.disclosed_indexes()?;
+            if messages.len() != indexes.len() {
+                return Err(AttributesError::InvalidDisclosedIndexes(format!(
+                    "{} disclosed messages for {} indexes",
+                    messages.len(),
+                    indexes.len()
+                ))
+                .into());
+            }
+        }
+        Ok(messages)
+    }
+    /// the disclosed indexes of a proof are stored using the
+    /// AttrId::DisclosedIndexes attribute id, in ascending order
+    fn disclosed_indexes(&self) -> Result<Vec<usize>, Error> {
+        if !self.scheme.is_proof() {
+            return Err(AttributesError::InvalidScheme(self.scheme.code()).into());
+        }
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::DisclosedIndexes)
+            .ok_or(AttributesError::MissingDisclosedIndexes)?;
+        let indexes = DisclosedIndexes::try_from(v.as_slice())?.0;
+        if indexes.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(AttributesError::InvalidDisclosedIndexes(
+                "indexes must be unique and in ascending order".to_string(),
+            )
+            .into());
+        }
+        Ok(indexes)
+    }
+    /// the presentation header of a proof is stored using the
+    /// AttrId::PresentationHeader attribute id. it is empty if none was given.
+    fn presentation_header(&self) -> Result<Vec<u8>, Error> {
+        if !self.scheme.is_proof() {
+            return Err(AttributesError::InvalidScheme(self.scheme.code()).into());
+        }
+        Ok(self
+            .ms
+            .attributes
+            .get(&AttrId::PresentationHeader)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
@@ -1,16 +1,20 @@
 // SPDX-License-Idnetifier: Apache-2.0
 use crate::{
-    error::{AttributesError, ConversionsError, SharesError},
-    AttrId, AttrView, Builder, ConvView, DataView, Error, Multisig, ThresholdAttrView,
-    ThresholdView, Views,
+    error::{AggregateError, AttributesError, ConversionsError, SharesError},
+    AggregateView, AttrId, AttrView, Builder, ConvView, DataView, Error, Multisig,
+    ThresholdAttrView, ThresholdView, Views,
 };
 use blsful::{
-    vsss_rs::Share, Bls12381G1Impl, Bls12381G2Impl, Signature, SignatureSchemes, SignatureShare,
+    inner_types::{G1Projective, G2Projective, GroupEncoding},
+    vsss_rs::Share,
+    Bls12381G1Impl, Bls12381G2Impl, Signature, SignatureSchemes, SignatureShare,
 };
 use multicodec::Codec;
 use multitrait::{EncodeInto, TryDecodeFrom};
 use multiutil::{Varbytes, Varuint};
-use std::{collections::BTreeMap, fmt};
+use std::{collections::BTreeMap, fmt, ops::Add};
+
+pub use crate::views::aggregate::{Messages, SignerKeyIds};
 
 /// the name used to identify these signatures in non-Multikey formats
 pub const ALGORITHM_NAME_G1: &str = "bls12_381-g1@multisig";
@@ -20,6 +24,10 @@ pub const ALGORITHM_NAME_G1_SHARE: &str = "bls12_381-g1-share@multisig";
 pub const ALGORITHM_NAME_G2: &str = "bls12_381-g2@multisig";
 /// the name used to identify these signatures in non-Multikey formats
 pub const ALGORITHM_NAME_G2_SHARE: &str = "bls12_381-g2-share@multisig";
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME_G1_AGGREGATE: &str = "bls12_381-g1-aggregate@multisig";
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME_G2_AGGREGATE: &str = "bls12_381-g2-aggregate@multisig";
 
 /// The different signature scheme methods offered in the blsful BLS crate
 #[repr(u8)]
@@ -199,6 +207,59 @@ impl<'a> TryDecodeFrom<'a> for SigCombined {
     }
 }
 
+/// tuple of aggregate signature data
+#[derive(Clone)]
+pub struct SigAggregate(
+    /// signature scheme
+    pub SchemeTypeId,
+    /// aggregate signature bytes
+    pub Vec<u8>,
+    /// signer key identifiers
+    pub SignerKeyIds,
+    /// messages, empty if every signer signed the same message
+    pub Messages,
+);
+
+impl From<SigAggregate> for Vec<u8> {
+    fn from(val: SigAggregate) -> Self {
+        let mut v = Vec::default();
+        // add in the signature type id
+        v.append(&mut val.0.into());
+        // add in the signature bytes
+        v.append(&mut Varbytes(val.1.clone()).into());
+        // add in the signer key identifiers
+        v.append(&mut val.2.into());
+        // add in the messages
+        v.append(&mut val.3.into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigAggregate {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (sig, _) = Self::try_decode_from(bytes)?;
+        Ok(sig)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigAggregate {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signature type
+        let (sig_type, ptr) = SchemeTypeId::try_decode_from(bytes)?;
+        // try to decode the signature bytes
+        let (sig_data, ptr) = Varbytes::try_decode_from(ptr)?;
+        // try to decode the signer key identifiers
+        let (key_ids, ptr) = SignerKeyIds::try_decode_from(ptr)?;
+        // try to decode the messages
+        let (messages, ptr) = Messages::try_decode_from(ptr)?;
+        Ok((Self(sig_type, sig_data.to_inner(), key_ids, messages), ptr))
+    }
+}
+
 /// tuple of signature share data with threshold attributes
 #[derive(Clone)]
 pub struct SigShare(
@@ -317,6 +378,30 @@ impl<'a> TryDecodeFrom<'a> for ThresholdData {
     }
 }
 
+// decode a compressed signature point
+fn point<G: GroupEncoding>(bytes: &[u8]) -> Result<G, Error> {
+    let mut repr = G::Repr::default();
+    if repr.as_ref().len() != bytes.len() {
+        return Err(AttributesError::InvalidSignatureLength(bytes.len()).into());
+    }
+    repr.as_mut().copy_from_slice(bytes);
+    Option::<G>::from(G::from_bytes(&repr)).ok_or_else(|| {
+        AggregateError::AggregateFailed("invalid signature point".to_string()).into()
+    })
+}
+
+// add a signature to the aggregate signature, if any
+fn aggregate<G>(agg: Option<&Vec<u8>>, sig: &[u8]) -> Result<Vec<u8>, Error>
+where
+    G: GroupEncoding + Add<Output = G>,
+{
+    let mut sum = point::<G>(sig)?;
+    if let Some(agg) = agg {
+        sum = point::<G>(agg)? + sum;
+    }
+    Ok(sum.to_bytes().as_ref().to_vec())
+}
+
 pub(crate) struct View<'a> {
     ms: &'a Multisig,
 }
@@ -379,6 +464,31 @@ impl ConvView for View<'_> {
         let scheme_type = SchemeTypeId::try_from(av.scheme()?)?;
 
         match self.ms.codec {
+            Codec::Bls12381G1Msig | Codec::Bls12381G2Msig
+                if self.ms.attributes.contains_key(&AttrId::SignerKeyIds) =>
+            {
+                // create the aggregate sig tuple
+                let key_ids = SignerKeyIds(self.signer_key_ids()?);
+                let messages = match self.ms.attributes.get(&AttrId::Messages) {
+                    Some(_) => Messages(self.messages()?),
+                    None => Messages::default(),
+                };
+                let sig_data: Vec<u8> =
+                    SigAggregate(scheme_type, sig_bytes, key_ids, messages).into();
+                let name = match self.ms.codec {
+                    Codec::Bls12381G1Msig => ALGORITHM_NAME_G1_AGGREGATE,
+                    _ => ALGORITHM_NAME_G2_AGGREGATE,
+                };
+
+                Ok(ssh_key::Signature::new(
+                    ssh_key::Algorithm::Other(
+                        ssh_key::AlgorithmName::new(name)
+                            .map_err(|e| ConversionsError::Ssh(e.into()))?,
+                    ),
+                    sig_data,
+                )
+                .map_err(|e| ConversionsError::Ssh(e.into()))?)
+            }
             Codec::Bls12381G1Msig => {
                 // create the combined sig tuple
                 let sig_data: Vec<u8> = SigCombined(scheme_type, sig_bytes).into();
@@ -735,3 +845,206 @@ impl ThresholdView for View<'_> {
         }
     }
 }
+
+impl View<'_> {
+    // check that the signature can be aggregated and sum it into the
+    // aggregate signature
+    fn sum_signature(&self, sig: &Multisig) -> Result<Vec<u8>, Error> {
+        // check the codec is correct for this function
+        match self.ms.codec {
+            Codec::Bls12381G1Msig | Codec::Bls12381G2Msig => {}
+            Codec::Bls12381G1ShareMsig | Codec::Bls12381G2ShareMsig => {
+                return Err(SharesError::IsASignatureShare.into())
+            }
+            _ => return Err(Error::UnsupportedAlgorithm(self.ms.codec.to_string())),
+        };
+        if sig.codec != self.ms.codec {
+            return Err(AggregateError::CodecMismatch(sig.codec).into());
+        }
+
+        let sig_bytes = {
+            let dv = sig.data_view()?;
+            dv.sig_bytes()?
+        };
+        let agg = self.ms.attributes.get(&AttrId::SigData);
+        match self.ms.codec {
+            Codec::Bls12381G1Msig => aggregate::<G1Projective>(agg, &sig_bytes),
+            _ => aggregate::<G2Projective>(agg, &sig_bytes),
+        }
+    }
+
+    // get the payload encoding of the aggregate signature, if this multisig
+    // doesn't have payload encoding set, use the value from the signature
+    fn aggregate_encoding(&self, sig: &Multisig) -> Result<Option<Codec>, Error> {
+        let av = self.ms.attr_view()?;
+        match av.payload_encoding() {
+            Ok(encoding) => Ok(Some(encoding)),
+            Err(_) => {
+                let av = sig.attr_view()?;
+                Ok(av.payload_encoding().ok())
+            }
+        }
+    }
+}
+
+/// trait for aggregating signatures from distinct signers
+impl AggregateView for View<'_> {
+    /// get the number of signers in the aggregate signature
+    fn signer_count(&self) -> Result<usize, Error> {
+        let count = self
+            .ms
+            .attributes
+            .get(&AttrId::SignerCount)
+            .ok_or(AttributesError::MissingSignerCount)?;
+        Ok(Varuint::<usize>::try_from(count.as_slice())?.to_inner())
+    }
+    /// get the key identifiers of the signers in the order they were added
+    fn signer_key_ids(&self) -> Result<Vec<Vec<u8>>, Error> {
+        let key_ids = self
+            .ms
+            .attributes
+            .get(&AttrId::SignerKeyIds)
+            .ok_or(AttributesError::MissingSignerKeyIds)?;
+        let key_ids = SignerKeyIds::try_from(key_ids.as_slice())?;
+        if key_ids.0.len() != self.signer_count()? {
+            return Err(AggregateError::SignerCountMismatch.into());
+        }
+        Ok(key_ids.0)
+    }
+    /// get the messages signed by each signer in the order they were added
+    fn messages(&self) -> Result<Vec<Vec<u8>>, Error> {
+        let messages = self
+            .ms
+            .attributes
+            .get(&AttrId::Messages)
+            .ok_or(AttributesError::MissingMessages)?;
+        let messages = Messages::try_from(messages.as_slice())?;
+        if messages.0.len() != self.signer_count()? {
+            return Err(AggregateError::SignerCountMismatch.into());
+        }
+        Ok(messages.0)
+    }
+    /// add a signature and return the Multisig with the signature summed into
+    /// the aggregate signature
+    fn add_signature(&self, key_id: &[u8], sig: &Multisig) -> Result<Multisig, Error> {
+        let sig_bytes = self.sum_signature(sig)?;
+        if self.ms.attributes.contains_key(&AttrId::Messages) {
+            return Err(AggregateError::MixedAggregate.into());
+        }
+
+        // summing signatures over the same message is only safe from rogue
+        // key attacks when every signer has proven possession of their key
+        let av = sig.attr_view()?;
+        if SchemeTypeId::try_from(av.scheme()?)? != SchemeTypeId::ProofOfPossession {
+            return Err(AggregateError::ProofOfPossessionRequired.into());
+        }
+        let av = self.ms.attr_view()?;
+        if let Ok(scheme) = av.scheme() {
+            if SchemeTypeId::try_from(scheme)? != SchemeTypeId::ProofOfPossession {
+                return Err(AggregateError::ProofOfPossessionRequired.into());
+            }
+        }
+
+        // every signer must have signed the same message
+        let message = if self.ms.message.is_empty() {
+            &sig.message
+        } else {
+            if !sig.message.is_empty() && sig.message != self.ms.message {
+                return Err(AggregateError::MessageMismatch.into());
+            }
+            &self.ms.message
+        };
+
+        // add the signer's key identifier
+        let mut key_ids = match self.ms.attributes.get(&AttrId::SignerKeyIds) {
+            Some(v) => SignerKeyIds::try_from(v.as_slice())?,
+            None => SignerKeyIds::default(),
+        };
+        if key_ids.0.iter().any(|k| k.as_slice() == key_id) {
+            return Err(AggregateError::DuplicateSigner.into());
+        }
+        key_ids.0.push(key_id.to_vec());
+        let count = key_ids.0.len();
+
+        let key_ids: Vec<u8> = key_ids.into();
+        let builder = Builder::new(self.ms.codec)
+            .with_message_bytes(message)
+            .with_scheme(SchemeTypeId::ProofOfPossession.into())
+            .with_signature_bytes(&sig_bytes)
+            .with_signer_count(count)
+            .with_signer_key_ids(&key_ids);
+
+        if let Some(encoding) = self.aggregate_encoding(sig)? {
+            builder.with_payload_encoding(encoding).try_build()
+        } else {
+            builder.try_build()
+        }
+    }
+    /// add a signature and return the Multisig with the signature summed into
+    /// the aggregate signature and its message appended to the messages
+    fn add_message_signature(&self, key_id: &[u8], sig: &Multisig) -> Result<Multisig, Error> {
+        let sig_bytes = self.sum_signature(sig)?;
+        if !self.ms.message.is_empty()
+            || (self.ms.attributes.contains_key(&AttrId::SignerKeyIds)
+                && !self.ms.attributes.contains_key(&AttrId::Messages))
+        {
+            return Err(AggregateError::MixedAggregate.into());
+        }
+        if sig.message.is_empty() {
+            return Err(AggregateError::MissingMessage.into());
+        }
+
+        // every signature must use the same scheme
+        let scheme = {
+            let av = sig.attr_view()?;
+            SchemeTypeId::try_from(av.scheme()?)?
+        };
+        let av = self.ms.attr_view()?;
+        if let Ok(s) = av.scheme() {
+            if SchemeTypeId::try_from(s)? != scheme {
+                return Err(AggregateError::SchemeMismatch.into());
+            }
+        }
+
+        // add the signer's key identifier and message
+        let mut key_ids = match self.ms.attributes.get(&AttrId::SignerKeyIds) {
+            Some(v) => SignerKeyIds::try_from(v.as_slice())?,
+            None => SignerKeyIds::default(),
+        };
+        let mut messages = match self.ms.attributes.get(&AttrId::Messages) {
+            Some(v) => Messages::try_from(v.as_slice())?,
+            None => Messages::default(),
+        };
+        // only message augmentation binds each message to the signer's key,
+        // the other schemes need distinct messages to be secure
+        if scheme != SchemeTypeId::MessageAugmentation && messages.0.contains(&sig.message) {
+            return Err(AggregateError::DuplicateMessage.into());
+        }
+        if key_ids
+            .0
+            .iter()
+            .zip(&messages.0)
+            .any(|(k, m)| k.as_slice() == key_id && *m == sig.message)
+        {
+            return Err(AggregateError::DuplicateSigner.into());
+        }
+        key_ids.0.push(key_id.to_vec());
+        messages.0.push(sig.message.clone());
+        let count = key_ids.0.len();
+
+        let key_ids: Vec<u8> = key_ids.into();
+        let messages: Vec<u8> = messages.into();
+        let builder = Builder::new(self.ms.codec)
+            .with_scheme(scheme.into())
+            .with_signature_bytes(&sig_bytes)
+            .with_signer_count(count)
+            .with_signer_key_ids(&key_ids)
+            .with_messages(&messages);
+
+        if let Some(encoding) = self.aggregate_encoding(sig)? {
+            builder.with_payload_encoding(encoding).try_build()
+        } else {
+            builder.try_build()
+        }
+    }
+}
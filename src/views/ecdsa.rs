@@ -0,0 +1,411 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    views::{p256, p384, p521, secp256k1},
+    AttrId, EcdsaView, Error, Multisig, Views,
+};
+use multicodec::Codec;
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::Varuint;
+use ssh_encoding::{Decode, Encode, Reader};
+use ssh_key::Mpint;
+use std::fmt;
+
+/// the ASN.1 DER INTEGER tag
+const DER_INTEGER: u8 = 0x02;
+/// the ASN.1 DER SEQUENCE tag
+const DER_SEQUENCE: u8 = 0x30;
+/// the ASN.1 DER long form length prefix for a single length byte
+const DER_LENGTH_1: u8 = 0x81;
+
+/// The encodings that ECDSA signatures arrive in. The signature data is
+/// always stored as the raw r||s bytes; this records the original encoding.
+/// Multisigs without a signature encoding attribute arrived as raw bytes.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Hash, Ord, PartialOrd, PartialEq, Eq)]
+pub enum SignatureEncoding {
+    /// fixed-width r||s
+    #[default]
+    Raw,
+    /// ASN.1 DER SEQUENCE { r, s }
+    Der,
+}
+
+impl SignatureEncoding {
+    /// Get the code for the signature encoding
+    pub fn code(&self) -> u8 {
+        (*self).into()
+    }
+
+    /// Convert the signature encoding to &str
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Raw => "raw",
+            Self::Der => "der",
+        }
+    }
+}
+
+impl From<SignatureEncoding> for u8 {
+    fn from(val: SignatureEncoding) -> Self {
+        val as u8
+    }
+}
+
+impl TryFrom<u8> for SignatureEncoding {
+    type Error = Error;
+
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            0 => Ok(Self::Raw),
+            1 => Ok(Self::Der),
+            _ => Err(AttributesError::InvalidAttributeValue(c).into()),
+        }
+    }
+}
+
+impl From<SignatureEncoding> for Vec<u8> {
+    fn from(val: SignatureEncoding) -> Self {
+        val.code().encode_into()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SignatureEncoding {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (encoding, _) = Self::try_decode_from(bytes)?;
+        Ok(encoding)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SignatureEncoding {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (code, ptr) = u8::try_decode_from(bytes)?;
+        Ok((Self::try_from(code)?, ptr))
+    }
+}
+
+impl TryFrom<&str> for SignatureEncoding {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "raw" => Ok(Self::Raw),
+            "der" => Ok(Self::Der),
+            _ => Err(AttributesError::InvalidAttributeName(s.to_string()).into()),
+        }
+    }
+}
+
+impl TryFrom<&Multisig> for SignatureEncoding {
+    type Error = Error;
+
+    /// get the signature encoding from the Multisig, defaulting to raw when
+    /// the signature encoding attribute is absent
+    fn try_from(ms: &Multisig) -> Result<Self, Self::Error> {
+        match ms.attributes.get(&AttrId::SigEncoding) {
+            Some(v) => Self::try_from(Varuint::<u8>::try_from(v.as_slice())?.to_inner()),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl fmt::Display for SignatureEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// get the scalar size of the curve used by the ECDSA codec
+pub(crate) fn scalar_size(codec: Codec) -> Result<usize, Error> {
+    match codec {
+        Codec::Es256Msig => Ok(p256::SCALAR_SIZE),
+        Codec::Es384Msig => Ok(p384::SCALAR_SIZE),
+        Codec::Es521Msig => Ok(p521::SCALAR_SIZE),
+        Codec::Es256KMsig => Ok(secp256k1::SCALAR_SIZE),
+        _ => Err(AttributesError::UnsupportedCodec(codec).into()),
+    }
+}
+
+/// get the big endian group order of the curve used by the ECDSA codec
+fn curve_order(codec: Codec) -> Result<&'static [u8], Error> {
+    match codec {
+        Codec::Es256Msig => Ok(&p256::CURVE_ORDER),
+        Codec::Es384Msig => Ok(&p384::CURVE_ORDER),
+        Codec::Es521Msig => Ok(&p521::CURVE_ORDER),
+        Codec::Es256KMsig => Ok(&secp256k1::CURVE_ORDER),
+        _ => Err(AttributesError::UnsupportedCodec(codec).into()),
+    }
+}
+
+/// check if the Multisig holds a plain or recoverable ECDSA r||s signature
+fn is_ecdsa(ms: &Multisig) -> Result<bool, Error> {
+    Ok(match ms.codec {
+        Codec::Es256Msig => p256::SchemeTypeId::try_from(ms)? == p256::SchemeTypeId::Ecdsa,
+        Codec::Es384Msig | Codec::Es521Msig => true,
+        Codec::Es256KMsig => matches!(
+            secp256k1::SchemeTypeId::try_from(ms)?,
+            secp256k1::SchemeTypeId::Ecdsa | secp256k1::SchemeTypeId::EcdsaRecoverable
+        ),
+        _ => false,
+    })
+}
+
+/// check that the ECDSA signature in the Multisig, if it holds one, is not a
+/// malleable high-S signature and has no zero or out of range r or s. Multisigs without signature data, or that are
+/// accumulating threshold signature shares, are not checked.
+pub(crate) fn check_low_s(ms: &Multisig) -> Result<(), Error> {
+    if !ms.attributes.contains_key(&AttrId::SigData)
+        || ms.attributes.contains_key(&AttrId::ThresholdData)
+    {
+        return Ok(());
+    }
+    if is_ecdsa(ms)? && !View::try_from(ms)?.is_low_s()? {
+        return Err(AttributesError::HighS.into());
+    }
+    Ok(())
+}
+
+/// compute n - s for the big endian s and group order n of the same width
+fn negate(s: &[u8], n: &[u8]) -> Vec<u8> {
+    let mut v = vec![0u8; n.len()];
+    let mut borrow = 0u16;
+    for ((v, n), s) in v.iter_mut().zip(n).zip(s).rev() {
+        let d = 0x100 + *n as u16 - *s as u16 - borrow;
+        *v = d as u8;
+        borrow = 1 - (d >> 8);
+    }
+    v
+}
+
+/// check that the raw signature is the fixed-width r||s form for a curve with
+/// the given scalar size
+pub(crate) fn check_sig_size(sig: &[u8], scalar_size: usize) -> Result<(), Error> {
+    if sig.len() != scalar_size * 2 {
+        return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+    }
+    Ok(())
+}
+
+/// convert a raw fixed-width r||s signature into the pair of SSH mpints used
+/// as the signature blob for the ecdsa-sha2-* SSH algorithms (RFC 5656)
+pub(crate) fn to_ssh_sig_data(sig: &[u8], scalar_size: usize) -> Result<Vec<u8>, Error> {
+    check_sig_size(sig, scalar_size)?;
+    let (r, s) = sig.split_at(scalar_size);
+    let mut v = Vec::default();
+    for c in [r, s] {
+        let mpint = Mpint::from_positive_bytes(c).map_err(|e| ConversionsError::Ssh(e.into()))?;
+        mpint
+            .encode(&mut v)
+            .map_err(|e| ConversionsError::Ssh(e.into()))?;
+    }
+    Ok(v)
+}
+
+/// convert the pair of SSH mpints from an ecdsa-sha2-* SSH signature blob into
+/// the raw fixed-width r||s signature
+pub(crate) fn from_ssh_sig_data(data: &[u8], scalar_size: usize) -> Result<Vec<u8>, Error> {
+    let mut reader = data;
+    let mut sig = vec![0u8; scalar_size * 2];
+    for i in 0..2 {
+        let mpint = Mpint::decode(&mut reader).map_err(|e| ConversionsError::Ssh(e.into()))?;
+        let c = mpint
+            .as_positive_bytes()
+            .ok_or(ConversionsError::Ssh(ssh_key::Error::Crypto.into()))?;
+        if c.len() > scalar_size {
+            return Err(AttributesError::InvalidSignatureLength(c.len()).into());
+        }
+        // left pad each component out to the scalar size
+        let end = (i + 1) * scalar_size;
+        sig[end - c.len()..end].copy_from_slice(c);
+    }
+    reader
+        .finish(())
+        .map_err(|e| ConversionsError::Ssh(e.into()))?;
+    Ok(sig)
+}
+
+/// encode the DER length of the contents
+fn der_length(len: usize, v: &mut Vec<u8>) {
+    if len >= 0x80 {
+        v.push(DER_LENGTH_1);
+    }
+    v.push(len as u8);
+}
+
+/// split the contents of the DER value with the given tag off of the front of
+/// the bytes, rejecting non-minimal lengths
+fn der_split(bytes: &[u8], tag: u8) -> Result<(&[u8], &[u8]), Error> {
+    let truncated = || ConversionsError::Der("truncated".to_string());
+    let (t, ptr) = bytes.split_first().ok_or_else(truncated)?;
+    if *t != tag {
+        return Err(ConversionsError::Der(format!("unexpected tag {:#04x}", t)).into());
+    }
+    let (l, ptr) = ptr.split_first().ok_or_else(truncated)?;
+    let (len, ptr) = match *l {
+        l if l < 0x80 => (l as usize, ptr),
+        DER_LENGTH_1 => {
+            let (l, ptr) = ptr.split_first().ok_or_else(truncated)?;
+            // lengths below 0x80 must use the short form
+            if *l < 0x80 {
+                return Err(ConversionsError::Der("non-minimal length".to_string()).into());
+            }
+            (*l as usize, ptr)
+        }
+        _ => return Err(ConversionsError::Der("unsupported length".to_string()).into()),
+    };
+    if ptr.len() < len {
+        return Err(truncated().into());
+    }
+    Ok(ptr.split_at(len))
+}
+
+/// strip the sign byte from a minimally encoded, positive DER INTEGER
+fn der_unsigned(int: &[u8]) -> Result<&[u8], Error> {
+    match int {
+        [] => Err(ConversionsError::Der("empty integer".to_string()).into()),
+        [b, ..] if b & 0x80 != 0 => {
+            Err(ConversionsError::Der("negative integer".to_string()).into())
+        }
+        [0] => Err(ConversionsError::Der("zero integer".to_string()).into()),
+        // a leading zero is only allowed when the next byte has its high bit set
+        [0, b, ..] if b & 0x80 == 0 => {
+            Err(ConversionsError::Der("non-minimal integer".to_string()).into())
+        }
+        [0, rest @ ..] => Ok(rest),
+        _ => Ok(int),
+    }
+}
+
+/// convert a raw fixed-width r||s signature into the ASN.1 DER
+/// SEQUENCE { r, s } encoding
+pub(crate) fn to_der(sig: &[u8], scalar_size: usize) -> Result<Vec<u8>, Error> {
+    check_sig_size(sig, scalar_size)?;
+    let (r, s) = sig.split_at(scalar_size);
+    let mut ints = Vec::default();
+    for c in [r, s] {
+        // strip the leading zeros and restore one if the high bit is set
+        let c = match c.iter().position(|b| *b != 0) {
+            Some(i) => &c[i..],
+            None => {
+                return Err(AttributesError::InvalidSignature(
+                    "ECDSA signature component is zero".to_string(),
+                )
+                .into())
+            }
+        };
+        let pad = c[0] & 0x80 != 0;
+        ints.push(DER_INTEGER);
+        der_length(c.len() + pad as usize, &mut ints);
+        if pad {
+            ints.push(0);
+        }
+        ints.extend_from_slice(c);
+    }
+    let mut v = vec![DER_SEQUENCE];
+    der_length(ints.len(), &mut v);
+    v.append(&mut ints);
+    Ok(v)
+}
+
+/// convert a strict ASN.1 DER SEQUENCE { r, s } signature into the raw
+/// fixed-width r||s signature
+pub(crate) fn from_der(der: &[u8], scalar_size: usize) -> Result<Vec<u8>, Error> {
+    let (seq, rest) = der_split(der, DER_SEQUENCE)?;
+    if !rest.is_empty() {
+        return Err(ConversionsError::Der("trailing data".to_string()).into());
+    }
+    let (r, seq) = der_split(seq, DER_INTEGER)?;
+    let (s, seq) = der_split(seq, DER_INTEGER)?;
+    if !seq.is_empty() {
+        return Err(ConversionsError::Der("trailing data in sequence".to_string()).into());
+    }
+    let mut sig = vec![0u8; scalar_size * 2];
+    for (i, c) in [r, s].into_iter().enumerate() {
+        let c = der_unsigned(c)?;
+        if c.len() > scalar_size {
+            return Err(AttributesError::InvalidSignatureLength(c.len()).into());
+        }
+        // left pad each component out to the scalar size
+        let end = (i + 1) * scalar_size;
+        sig[end - c.len()..end].copy_from_slice(c);
+    }
+    Ok(sig)
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+    scalar_size: usize,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        let scalar_size = scalar_size(ms.codec)?;
+        // only plain and recoverable ECDSA signatures have an r||s signature
+        if !is_ecdsa(ms)? {
+            return Err(AttributesError::InvalidScheme(ms.attr_view()?.scheme()?).into());
+        }
+        Ok(Self { ms, scalar_size })
+    }
+}
+
+impl View<'_> {
+    /// get the fixed-width r||s signature data
+    fn sig(&self) -> Result<Vec<u8>, Error> {
+        let dv = self.ms.data_view()?;
+        let sig = dv.sig_bytes()?;
+        check_sig_size(&sig, self.scalar_size)?;
+        Ok(sig)
+    }
+}
+
+impl EcdsaView for View<'_> {
+    /// for ECDSA Multisigs, the signature encoding is stored using the
+    /// AttrId::SigEncoding attribute id, raw if the attribute is absent
+    fn signature_encoding(&self) -> Result<SignatureEncoding, Error> {
+        SignatureEncoding::try_from(self.ms)
+    }
+    /// convert the raw r||s signature data to DER
+    fn to_der(&self) -> Result<Vec<u8>, Error> {
+        let dv = self.ms.data_view()?;
+        to_der(&dv.sig_bytes()?, self.scalar_size)
+    }
+    /// s is low if it is no greater than n - s, where n is the group order.
+    /// r and s must both be in 1..n or the signature is invalid.
+    fn is_low_s(&self) -> Result<bool, Error> {
+        let sig = self.sig()?;
+        let n = curve_order(self.ms.codec)?;
+        let (r, s) = sig.split_at(self.scalar_size);
+        if [r, s].iter().any(|c| c.iter().all(|b| *b == 0) || *c >= n) {
+            return Err(AttributesError::InvalidSignature(
+                "ECDSA signature component out of range".to_string(),
+            )
+            .into());
+        }
+        Ok(s <= negate(s, n).as_slice())
+    }
+    /// replace a high s with n - s. the new signature matches the negated R
+    /// point, which has the opposite y parity, so the recovery id of a
+    /// recoverable signature flips too.
+    fn normalize_s(&self) -> Result<Multisig, Error> {
+        let mut ms = self.ms.clone();
+        if self.is_low_s()? {
+            return Ok(ms);
+        }
+        let mut sig = self.sig()?;
+        let s = negate(&sig[self.scalar_size..], curve_order(self.ms.codec)?);
+        sig[self.scalar_size..].copy_from_slice(&s);
+        ms.attributes.insert(AttrId::SigData, sig);
+        if let Some(v) = self.ms.attributes.get(&AttrId::RecoveryId) {
+            let recovery_id = Varuint::<u8>::try_from(v.as_slice())?.to_inner();
+            ms.attributes
+                .insert(AttrId::RecoveryId, Varuint(recovery_id ^ 1).into());
+        }
+        Ok(ms)
+    }
+}
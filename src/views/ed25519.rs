@@ -1,10 +1,16 @@
 // SPDX-License-Idnetifier: Apache-2.0
 use crate::{
     error::{AttributesError, ConversionsError},
+    views::eddsa::{SchemeTypeId, SigContext, MAX_CONTEXT_SIZE},
     AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
 };
 use multicodec::Codec;
 
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME_PH: &str = "ed25519ph@multisig";
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME_CTX: &str = "ed25519ctx@multisig";
+
 pub(crate) struct View<'a> {
     ms: &'a Multisig,
 }
@@ -13,7 +19,13 @@ impl<'a> TryFrom<&'a Multisig> for View<'a> {
     type Error = Error;
 
     fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
-        Ok(Self { ms })
+        // only the RFC 8032 Ed25519 variants are verified as Ed25519
+        match SchemeTypeId::try_from(ms)? {
+            SchemeTypeId::Ed25519 | SchemeTypeId::Ed25519ph | SchemeTypeId::Ed25519ctx => {
+                Ok(Self { ms })
+            }
+            scheme => Err(Error::UnsupportedAlgorithm(scheme.to_string())),
+        }
     }
 }
 
@@ -29,9 +41,31 @@ impl AttrView for View<'_> {
         let encoding = Codec::try_from(v.as_slice())?;
         Ok(encoding)
     }
-    /// EdDSA signatures only have one scheme so this is meaningless
+    /// EdDSA signatures without a scheme attribute are Ed25519
     fn scheme(&self) -> Result<u8, Error> {
-        Ok(0)
+        Ok(SchemeTypeId::try_from(self.ms)?.code())
+    }
+    /// Ed25519ph and Ed25519ctx signatures are bound to a context string of
+    /// up to 255 bytes which is stored using the AttrId::Context attribute id.
+    /// the context is empty for Ed25519ph if the attribute is absent and must
+    /// not be empty for Ed25519ctx. pure Ed25519 signatures have no context.
+    fn context(&self) -> Result<Vec<u8>, Error> {
+        let context = self.ms.attributes.get(&AttrId::Context);
+        let context = match SchemeTypeId::try_from(self.ms)? {
+            SchemeTypeId::Ed25519ph => context.cloned().unwrap_or_default(),
+            SchemeTypeId::Ed25519ctx => {
+                let context = context.ok_or(AttributesError::MissingContext)?;
+                if context.is_empty() {
+                    return Err(AttributesError::InvalidContextLength(0).into());
+                }
+                context.clone()
+            }
+            _ => return Err(AttributesError::MissingContext.into()),
+        };
+        if context.len() > MAX_CONTEXT_SIZE {
+            return Err(AttributesError::InvalidContextLength(context.len()).into());
+        }
+        Ok(context)
     }
 }
 
@@ -44,6 +78,18 @@ impl DataView for View<'_> {
             .attributes
             .get(&AttrId::SigData)
             .ok_or(AttributesError::MissingSignature)?;
+        // make sure the context matches the Ed25519 variant so that the
+        // variants are never confused with each other
+        match SchemeTypeId::try_from(self.ms)? {
+            SchemeTypeId::Ed25519ph | SchemeTypeId::Ed25519ctx => {
+                self.context()?;
+            }
+            _ => {
+                if let Some(context) = self.ms.attributes.get(&AttrId::Context) {
+                    return Err(AttributesError::InvalidContextLength(context.len()).into());
+                }
+            }
+        }
         Ok(sig.clone())
     }
 }
@@ -54,9 +100,28 @@ impl ConvView for View<'_> {
         // get the signature data
         let dv = self.ms.data_view()?;
         let sig_bytes = dv.sig_bytes()?;
-        Ok(
-            ssh_key::Signature::new(ssh_key::Algorithm::Ed25519, sig_bytes)
-                .map_err(|e| ConversionsError::Ssh(e.into()))?,
+
+        // only pure Ed25519 signatures are standard SSH signatures
+        let name = match SchemeTypeId::try_from(self.ms)? {
+            SchemeTypeId::Ed25519ph => ALGORITHM_NAME_PH,
+            SchemeTypeId::Ed25519ctx => ALGORITHM_NAME_CTX,
+            _ => {
+                return Ok(
+                    ssh_key::Signature::new(ssh_key::Algorithm::Ed25519, sig_bytes)
+                        .map_err(|e| ConversionsError::Ssh(e.into()))?,
+                )
+            }
+        };
+
+        // create the context and sig tuple
+        let sig_data: Vec<u8> = SigContext(self.context()?, sig_bytes).into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(name).map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
         )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
     }
 }
@@ -0,0 +1,353 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AggregateError, AttributesError, ConversionsError},
+    views::{
+        aggregate::{Messages, SignerKeyIds},
+        eddsa::SchemeTypeId,
+    },
+    AggregateView, AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
+};
+use curve25519_dalek::{edwards::CompressedEdwardsY, traits::Identity, EdwardsPoint, Scalar};
+use multicodec::Codec;
+use multitrait::TryDecodeFrom;
+use multiutil::{Varbytes, Varuint};
+use sha2::{Digest, Sha512};
+
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME: &str = "ed25519-half-aggregate@multisig";
+
+/// the size of an Ed25519 signature in bytes
+pub const SIGNATURE_SIZE: usize = 64;
+
+/// the size of an Ed25519 public key, R value and s value in bytes
+pub const ELEMENT_SIZE: usize = 32;
+
+/// the domain separation tag for the aggregation coefficients
+const HALF_AGGREGATE_TAG: &[u8] = b"HalfAgg-Ed25519-SHA512-v1";
+
+/// tuple of the half-aggregate signature, the signers' public keys and the
+/// messages they signed
+#[derive(Clone)]
+pub struct SigHalfAggregate(
+    /// half-aggregate signature bytes, R_1 || ... || R_n || s
+    pub Vec<u8>,
+    /// signer public keys
+    pub SignerKeyIds,
+    /// signed messages
+    pub Messages,
+);
+
+impl From<SigHalfAggregate> for Vec<u8> {
+    fn from(val: SigHalfAggregate) -> Self {
+        let mut v = Vec::default();
+        // add in the signature bytes
+        v.append(&mut Varbytes(val.0.clone()).into());
+        // add in the signer public keys
+        v.append(&mut val.1.into());
+        // add in the messages
+        v.append(&mut val.2.into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigHalfAggregate {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (sig, _) = Self::try_decode_from(bytes)?;
+        Ok(sig)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigHalfAggregate {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signature bytes
+        let (sig_data, ptr) = Varbytes::try_decode_from(bytes)?;
+        // try to decode the signer public keys
+        let (keys, ptr) = SignerKeyIds::try_decode_from(ptr)?;
+        // try to decode the messages
+        let (messages, ptr) = Messages::try_decode_from(ptr)?;
+        Ok((Self(sig_data.to_inner(), keys, messages), ptr))
+    }
+}
+
+/// compute the aggregation coefficient for each signature (Chalkias et al.
+/// half-aggregation). the first coefficient is one and every later
+/// coefficient z_i is the SHA-512 hash, reduced mod L, of the tag followed by
+/// R_j || A_j || len(m_j) || m_j for each signature j up to and including i,
+/// and then i as four big-endian bytes. the message lengths are eight
+/// big-endian bytes. binding each coefficient to the R values, public keys
+/// and messages before it means no signature can be swapped out of the
+/// aggregate.
+pub(crate) fn coefficients(rs: &[&[u8]], keys: &[Vec<u8>], messages: &[Vec<u8>]) -> Vec<Scalar> {
+    let mut transcript = Sha512::new();
+    transcript.update(HALF_AGGREGATE_TAG);
+    rs.iter()
+        .zip(keys)
+        .zip(messages)
+        .enumerate()
+        .map(|(i, ((r, key), msg))| {
+            transcript.update(r);
+            transcript.update(key);
+            transcript.update((msg.len() as u64).to_be_bytes());
+            transcript.update(msg);
+            if i == 0 {
+                return Scalar::ONE;
+            }
+            let mut hasher = transcript.clone();
+            hasher.update((i as u32).to_be_bytes());
+            Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+        })
+        .collect()
+}
+
+/// compute the Ed25519 challenge for one signature (RFC 8032 5.1.7)
+fn challenge(r: &[u8], key: &[u8], msg: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(r);
+    hasher.update(key);
+    hasher.update(msg);
+    Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+}
+
+/// half-aggregate the Ed25519 signatures into R_1 || ... || R_n || s where s
+/// is the sum of each signature's s value scaled by its coefficient
+pub(crate) fn half_aggregate(
+    sigs: &[Multisig],
+    keys: &[Vec<u8>],
+) -> Result<(Vec<u8>, Messages), Error> {
+    if sigs.is_empty() {
+        return Err(AggregateError::AggregateFailed("no signatures".to_string()).into());
+    }
+    if sigs.len() != keys.len() {
+        return Err(AggregateError::SignerCountMismatch.into());
+    }
+
+    let mut sig_bytes = Vec::with_capacity(sigs.len());
+    let mut messages = Vec::with_capacity(sigs.len());
+    for (sig, key) in sigs.iter().zip(keys) {
+        // only pure Ed25519 signatures can be half-aggregated
+        if sig.codec != Codec::EddsaMsig {
+            return Err(AggregateError::CodecMismatch(sig.codec).into());
+        }
+        if SchemeTypeId::try_from(sig)? != SchemeTypeId::Ed25519 {
+            return Err(AggregateError::SchemeMismatch.into());
+        }
+        let dv = sig.data_view()?;
+        let b = dv.sig_bytes()?;
+        if b.len() != SIGNATURE_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(b.len()).into());
+        }
+        if sig.message.is_empty() {
+            return Err(AggregateError::MissingMessage.into());
+        }
+        if !valid_point(key) {
+            return Err(AggregateError::AggregateFailed("invalid public key".to_string()).into());
+        }
+        if !valid_point(&b[..ELEMENT_SIZE]) {
+            return Err(AttributesError::InvalidSignature("invalid R value".to_string()).into());
+        }
+        sig_bytes.push(b);
+        messages.push(sig.message.clone());
+    }
+
+    // sum the s values scaled by their coefficients
+    let rs: Vec<&[u8]> = sig_bytes.iter().map(|b| &b[..ELEMENT_SIZE]).collect();
+    let mut s = Scalar::ZERO;
+    for (b, z) in sig_bytes.iter().zip(coefficients(&rs, keys, &messages)) {
+        let mut si = [0u8; ELEMENT_SIZE];
+        si.copy_from_slice(&b[ELEMENT_SIZE..]);
+        let si = Option::<Scalar>::from(Scalar::from_canonical_bytes(si)).ok_or(
+            AttributesError::InvalidSignature("invalid s value".to_string()),
+        )?;
+        s += z * si;
+    }
+
+    let mut agg: Vec<u8> = rs.concat();
+    agg.extend_from_slice(s.as_bytes());
+    Ok((agg, Messages(messages)))
+}
+
+/// decompress the bytes as an Ed25519 point
+fn point(bytes: &[u8]) -> Option<EdwardsPoint> {
+    CompressedEdwardsY(<[u8; ELEMENT_SIZE]>::try_from(bytes).ok()?).decompress()
+}
+
+/// check that the bytes are a valid compressed Ed25519 point
+fn valid_point(bytes: &[u8]) -> bool {
+    point(bytes).is_some()
+}
+
+/// Verify an Ed25519 half-aggregate Multisig against the signers' public
+/// keys and messages it carries by checking that
+/// s * B == sum(z_i * (R_i + c_i * A_i)).
+pub fn verify(ms: &Multisig) -> Result<(), Error> {
+    if SchemeTypeId::try_from(ms)? != SchemeTypeId::Ed25519HalfAggregate {
+        return Err(AggregateError::SchemeMismatch.into());
+    }
+    let view = View::try_from(ms)?;
+    let keys = view.signer_key_ids()?;
+    let messages = view.messages()?;
+    let agg = view.sig_bytes()?;
+    let (rs, s) = agg.split_at(keys.len() * ELEMENT_SIZE);
+    let rs: Vec<&[u8]> = rs.chunks(ELEMENT_SIZE).collect();
+    let s = <[u8; ELEMENT_SIZE]>::try_from(s)
+        .ok()
+        .and_then(|s| Option::<Scalar>::from(Scalar::from_canonical_bytes(s)))
+        .ok_or(AttributesError::InvalidSignature(
+            "invalid s value".to_string(),
+        ))?;
+
+    let mut sum = EdwardsPoint::identity();
+    for (((r, key), msg), z) in rs
+        .iter()
+        .zip(&keys)
+        .zip(&messages)
+        .zip(coefficients(&rs, &keys, &messages))
+    {
+        let rp = point(r).ok_or(AttributesError::InvalidSignature(
+            "invalid R value".to_string(),
+        ))?;
+        let kp = point(key).ok_or(AttributesError::InvalidSignature(
+            "invalid public key".to_string(),
+        ))?;
+        sum += z * (rp + challenge(r, key, msg) * kp);
+    }
+    if EdwardsPoint::mul_base(&s) != sum {
+        return Err(AttributesError::InvalidSignature(
+            "half-aggregate verification failed".to_string(),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for Ed25519 half-aggregate Multisigs, the payload encoding is stored
+    /// using the AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// Ed25519 half-aggregates are identified by their EdDSA scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(SchemeTypeId::try_from(self.ms)?.code())
+    }
+}
+
+impl DataView for View<'_> {
+    /// For Ed25519 half-aggregate Multisig values, the R value of each
+    /// signature followed by the aggregate s value is stored using the
+    /// AttrId::SigData attribute id.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        if sig.len() != (self.signer_count()? + 1) * ELEMENT_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // create the half-aggregate sig tuple
+        let sig_data: Vec<u8> = SigHalfAggregate(
+            sig_bytes,
+            SignerKeyIds(self.signer_key_ids()?),
+            Messages(self.messages()?),
+        )
+        .into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(ALGORITHM_NAME)
+                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
+
+/// Ed25519 half-aggregates are built from all of the signatures at once
+impl AggregateView for View<'_> {
+    /// get the number of signatures in the half-aggregate
+    fn signer_count(&self) -> Result<usize, Error> {
+        let count = self
+            .ms
+            .attributes
+            .get(&AttrId::SignerCount)
+            .ok_or(AttributesError::MissingSignerCount)?;
+        Ok(Varuint::<usize>::try_from(count.as_slice())?.to_inner())
+    }
+    /// get the signers' public keys in signature order
+    fn signer_key_ids(&self) -> Result<Vec<Vec<u8>>, Error> {
+        let keys = self
+            .ms
+            .attributes
+            .get(&AttrId::SignerKeyIds)
+            .ok_or(AttributesError::MissingSignerKeyIds)?;
+        let keys = SignerKeyIds::try_from(keys.as_slice())?;
+        if keys.0.len() != self.signer_count()? {
+            return Err(AggregateError::SignerCountMismatch.into());
+        }
+        Ok(keys.0)
+    }
+    /// get the signed messages in signature order
+    fn messages(&self) -> Result<Vec<Vec<u8>>, Error> {
+        let messages = self
+            .ms
+            .attributes
+            .get(&AttrId::Messages)
+            .ok_or(AttributesError::MissingMessages)?;
+        let messages = Messages::try_from(messages.as_slice())?;
+        if messages.0.len() != self.signer_count()? {
+            return Err(AggregateError::SignerCountMismatch.into());
+        }
+        Ok(messages.0)
+    }
+    /// the aggregation coefficients depend on every signature so signatures
+    /// cannot be added one at a time
+    fn add_signature(&self, _key_id: &[u8], _sig: &Multisig) -> Result<Multisig, Error> {
+        Err(AggregateError::AggregateFailed(
+            "Ed25519 half-aggregates are built from a slice of signatures".to_string(),
+        )
+        .into())
+    }
+    /// the aggregation coefficients depend on every signature so signatures
+    /// cannot be added one at a time
+    fn add_message_signature(&self, _key_id: &[u8], _sig: &Multisig) -> Result<Multisig, Error> {
+        Err(AggregateError::AggregateFailed(
+            "Ed25519 half-aggregates are built from a slice of signatures".to_string(),
+        )
+        .into())
+    }
+}
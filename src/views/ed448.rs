@@ -0,0 +1,145 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    views::eddsa::SchemeTypeId,
+    AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
+};
+use multicodec::Codec;
+use ssh_encoding::{Decode, Encode, Reader};
+
+pub use crate::views::eddsa::{SigContext, MAX_CONTEXT_SIZE};
+
+/// the name used to identify these signatures in non-Multikey formats. it is
+/// used when converting to an ssh-key signature because ssh-key has no Ed448
+/// algorithm and only accepts additional algorithm names with a domain.
+pub const ALGORITHM_NAME: &str = "ed448@multisig";
+
+/// the SSH algorithm name for Ed448 signatures (RFC 8709)
+pub const SSH_ALGORITHM_NAME: &str = "ssh-ed448";
+
+/// the size of an Ed448 signature in bytes
+pub const SIGNATURE_SIZE: usize = 114;
+
+/// encode the Ed448 signature as an RFC 8709 ssh-ed448 signature blob, the
+/// algorithm name followed by the signature, each as an SSH string. ssh-ed448
+/// signatures are made with an empty context.
+pub fn to_ssh_ed448_signature(ms: &Multisig) -> Result<Vec<u8>, Error> {
+    let scheme = SchemeTypeId::try_from(ms)?;
+    if scheme != SchemeTypeId::Ed448 {
+        return Err(AttributesError::InvalidScheme(scheme.code()).into());
+    }
+    let view = View::try_from(ms)?;
+    if !view.context()?.is_empty() {
+        return Err(Error::UnsupportedAlgorithm(format!(
+            "{} with a context",
+            SSH_ALGORITHM_NAME
+        )));
+    }
+    let sig_bytes = view.sig_bytes()?;
+    let mut v = Vec::default();
+    SSH_ALGORITHM_NAME
+        .encode(&mut v)
+        .map_err(|e| ConversionsError::Ssh(e.into()))?;
+    sig_bytes
+        .encode(&mut v)
+        .map_err(|e| ConversionsError::Ssh(e.into()))?;
+    Ok(v)
+}
+
+/// decode an Ed448 SSH signature blob into its context and signature. both
+/// the RFC 8709 ssh-ed448 name and the ed448@multisig name are accepted.
+pub(crate) fn from_ssh_ed448_signature(blob: &[u8]) -> Result<SigContext, Error> {
+    let mut reader = blob;
+    let name = String::decode(&mut reader).map_err(|e| ConversionsError::Ssh(e.into()))?;
+    let data = Vec::<u8>::decode(&mut reader).map_err(|e| ConversionsError::Ssh(e.into()))?;
+    reader
+        .finish(())
+        .map_err(|e| ConversionsError::Ssh(e.into()))?;
+    match name.as_str() {
+        SSH_ALGORITHM_NAME => Ok(SigContext(Vec::default(), data)),
+        ALGORITHM_NAME => SigContext::try_from(data.as_slice()),
+        _ => Err(Error::UnsupportedAlgorithm(name)),
+    }
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for Ed448 Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// Ed448 signatures are identified by their EdDSA scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(SchemeTypeId::try_from(self.ms)?.code())
+    }
+    /// Ed448 signatures may be bound to a context string of up to 255 bytes
+    /// which is stored using the AttrId::Context attribute id. the context
+    /// is empty if the attribute is absent.
+    fn context(&self) -> Result<Vec<u8>, Error> {
+        let context = self
+            .ms
+            .attributes
+            .get(&AttrId::Context)
+            .cloned()
+            .unwrap_or_default();
+        if context.len() > MAX_CONTEXT_SIZE {
+            return Err(AttributesError::InvalidContextLength(context.len()).into());
+        }
+        Ok(context)
+    }
+}
+
+impl DataView for View<'_> {
+    /// For Ed448 Multisig values, the sig data is stored using the
+    /// AttrId::SigData attribute id.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        if sig.len() != SIGNATURE_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // create the context and sig tuple
+        let sig_data: Vec<u8> = SigContext(self.context()?, sig_bytes).into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(ALGORITHM_NAME)
+                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
@@ -0,0 +1,185 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{error::AttributesError, AttrId, Error, Multisig};
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::{Varbytes, Varuint};
+use std::fmt;
+
+/// the maximum size of an EdDSA context string in bytes (RFC 8032 5.1, 5.2)
+pub const MAX_CONTEXT_SIZE: usize = 255;
+
+/// The EdDSA and related Curve25519 Schnorr signature schemes carried by the
+/// EddsaMsig codec. Multisigs without a scheme attribute are pure Ed25519
+/// signatures.
+#[repr(u8)]
+#[derive(Clone, Copy, Default, Hash, Ord, PartialOrd, PartialEq, Eq)]
+pub enum SchemeTypeId {
+    /// Ed25519 (RFC 8032)
+    #[default]
+    Ed25519,
+    /// Ed448 (RFC 8032)
+    Ed448,
+    /// FROST(Ed25519, SHA-512) signature share (RFC 9591)
+    FrostShare,
+    /// Ed25519ph, pre-hashed with SHA-512 (RFC 8032)
+    Ed25519ph,
+    /// Ed25519ctx, bound to a non-empty context string (RFC 8032)
+    Ed25519ctx,
+    /// Half-aggregate of many Ed25519 signatures
+    Ed25519HalfAggregate,
+    /// XEdDSA, verified against an X25519 public key (Signal)
+    XEdDsa,
+    /// sr25519, Schnorr over Ristretto255 with a signing context label
+    /// (schnorrkel, Substrate). sr25519 is not EdDSA. It is carried here
+    /// because the multicodec table has no sr25519 signature codec, and like
+    /// XEdDSA it is a Schnorr signature over Curve25519 with a 64 byte R || s
+    /// encoding. The scheme tag keeps it apart from Ed25519: the Ed25519,
+    /// FROST and half-aggregate views all reject it.
+    Sr25519,
+}
+
+impl SchemeTypeId {
+    /// Get the code for the scheme
+    pub fn code(&self) -> u8 {
+        (*self).into()
+    }
+
+    /// Convert the scheme to &str
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Ed25519 => "ed25519",
+            Self::Ed448 => "ed448",
+            Self::FrostShare => "frost-ed25519-share",
+            Self::Ed25519ph => "ed25519ph",
+            Self::Ed25519ctx => "ed25519ctx",
+            Self::Ed25519HalfAggregate => "ed25519-half-aggregate",
+            Self::XEdDsa => "xeddsa",
+            Self::Sr25519 => "sr25519",
+        }
+    }
+}
+
+impl From<SchemeTypeId> for u8 {
+    fn from(val: SchemeTypeId) -> Self {
+        val as u8
+    }
+}
+
+impl TryFrom<u8> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            0 => Ok(Self::Ed25519),
+            1 => Ok(Self::Ed448),
+            2 => Ok(Self::FrostShare),
+            3 => Ok(Self::Ed25519ph),
+            4 => Ok(Self::Ed25519ctx),
+            5 => Ok(Self::Ed25519HalfAggregate),
+            6 => Ok(Self::XEdDsa),
+            7 => Ok(Self::Sr25519),
+            _ => Err(AttributesError::InvalidScheme(c).into()),
+        }
+    }
+}
+
+impl From<SchemeTypeId> for Vec<u8> {
+    fn from(val: SchemeTypeId) -> Self {
+        val.code().encode_into()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (id, _) = Self::try_decode_from(bytes)?;
+        Ok(id)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (code, ptr) = u8::try_decode_from(bytes)?;
+        Ok((Self::try_from(code)?, ptr))
+    }
+}
+
+impl TryFrom<&str> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "ed25519" => Ok(Self::Ed25519),
+            "ed448" => Ok(Self::Ed448),
+            "frost-ed25519-share" => Ok(Self::FrostShare),
+            "ed25519ph" => Ok(Self::Ed25519ph),
+            "ed25519ctx" => Ok(Self::Ed25519ctx),
+            "ed25519-half-aggregate" => Ok(Self::Ed25519HalfAggregate),
+            "xeddsa" => Ok(Self::XEdDsa),
+            "sr25519" => Ok(Self::Sr25519),
+            _ => Err(AttributesError::InvalidSchemeName(s.to_string()).into()),
+        }
+    }
+}
+
+impl TryFrom<&Multisig> for SchemeTypeId {
+    type Error = Error;
+
+    /// get the EdDSA scheme from the Multisig, defaulting to Ed25519 when the
+    /// scheme attribute is absent
+    fn try_from(ms: &Multisig) -> Result<Self, Self::Error> {
+        match ms.attributes.get(&AttrId::Scheme) {
+            Some(v) => Self::try_from(Varuint::<u8>::try_from(v.as_slice())?.to_inner()),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl fmt::Display for SchemeTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// tuple of the context string and signature data
+#[derive(Clone)]
+pub struct SigContext(
+    /// context string, empty if none
+    pub Vec<u8>,
+    /// signature bytes
+    pub Vec<u8>,
+);
+
+impl From<SigContext> for Vec<u8> {
+    fn from(val: SigContext) -> Self {
+        let mut v = Vec::default();
+        // add in the context string
+        v.append(&mut Varbytes(val.0.clone()).into());
+        // add in the signature bytes
+        v.append(&mut Varbytes(val.1.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigContext {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (sig, _) = Self::try_decode_from(bytes)?;
+        Ok(sig)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigContext {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the context string
+        let (context, ptr) = Varbytes::try_decode_from(bytes)?;
+        // try to decode the signature bytes
+        let (sig_data, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((Self(context.to_inner(), sig_data.to_inner()), ptr))
+    }
+}
@@ -0,0 +1,956 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError, SharesError},
+    views::{eddsa, secp256k1},
+    AttrId, AttrView, Builder, ConvView, DataView, Error, Multisig, ThresholdAttrView,
+    ThresholdView, Views,
+};
+use curve25519_dalek::{
+    edwards::CompressedEdwardsY,
+    traits::{Identity, IsIdentity},
+    EdwardsPoint, Scalar,
+};
+use k256::elliptic_curve::{
+    group::GroupEncoding,
+    hash2curve::{hash_to_field, ExpandMsgXmd},
+    ops::Reduce,
+    point::AffineCoordinates,
+    PrimeField,
+};
+use multicodec::Codec;
+use multitrait::TryDecodeFrom;
+use multiutil::{Varbytes, Varuint};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::BTreeMap;
+
+/// the name used to identify FROST Ed25519 signature shares in non-Multikey
+/// formats
+pub const ALGORITHM_NAME_ED25519_SHARE: &str = "frost-ed25519-share@multisig";
+
+/// the name used to identify FROST secp256k1 signature shares in
+/// non-Multikey formats
+pub const ALGORITHM_NAME_SECP256K1_SHARE: &str = "frost-secp256k1-share@multisig";
+/// the name used to identify BIP-340 compatible FROST secp256k1 signature
+/// shares in non-Multikey formats
+pub const ALGORITHM_NAME_SECP256K1_TR_SHARE: &str = "frost-secp256k1-tr-share@multisig";
+
+/// the size of a FROST Ed25519 signature share in bytes
+pub const ED25519_SHARE_SIZE: usize = 32;
+/// the size of a FROST secp256k1 signature share in bytes
+pub const SECP256K1_SHARE_SIZE: usize = 32;
+/// the largest number of signers, signers are identified by a non-zero u8
+pub const MAX_SIGNERS: usize = u8::MAX as usize;
+
+/// The FROST ciphersuites
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Ciphersuite {
+    /// FROST(Ed25519, SHA-512) producing Ed25519 signatures
+    Ed25519,
+    /// FROST(secp256k1, SHA-256) producing FROST Schnorr signatures
+    Secp256k1,
+    /// FROST(secp256k1, SHA-256) producing BIP-340 Schnorr signatures
+    Secp256k1Tr,
+}
+
+impl Ciphersuite {
+    /// the context string for the ciphersuite (RFC 9591 6.1 and 6.5)
+    fn context(&self) -> &'static [u8] {
+        match self {
+            Self::Ed25519 => b"FROST-ED25519-SHA512-v1",
+            Self::Secp256k1 => b"FROST-secp256k1-SHA256-v1",
+            Self::Secp256k1Tr => b"FROST-secp256k1-SHA256-TR-v1",
+        }
+    }
+
+    /// the scheme used by signature shares for the ciphersuite
+    fn share_scheme(&self) -> u8 {
+        match self {
+            Self::Ed25519 => eddsa::SchemeTypeId::FrostShare.code(),
+            Self::Secp256k1 => secp256k1::SchemeTypeId::FrostShare.code(),
+            Self::Secp256k1Tr => secp256k1::SchemeTypeId::FrostTaprootShare.code(),
+        }
+    }
+
+    /// the size of a signature share in bytes
+    fn share_size(&self) -> usize {
+        match self {
+            Self::Ed25519 => ED25519_SHARE_SIZE,
+            Self::Secp256k1 | Self::Secp256k1Tr => SECP256K1_SHARE_SIZE,
+        }
+    }
+
+    /// the name used to identify signature shares in non-Multikey formats
+    fn algorithm_name(&self) -> &'static str {
+        match self {
+            Self::Ed25519 => ALGORITHM_NAME_ED25519_SHARE,
+            Self::Secp256k1 => ALGORITHM_NAME_SECP256K1_SHARE,
+            Self::Secp256k1Tr => ALGORITHM_NAME_SECP256K1_TR_SHARE,
+        }
+    }
+
+    /// aggregate the signature shares from every signer in the signing
+    /// package into a signature
+    fn aggregate(
+        &self,
+        package: &SigningPackage,
+        shares: &BTreeMap<u8, SigShare>,
+        msg: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Ed25519 => ed25519_aggregate(package, shares, msg),
+            Self::Secp256k1 | Self::Secp256k1Tr => secp256k1_aggregate(*self, package, shares, msg),
+        }
+    }
+}
+
+/// tuple of a signer's round one nonce commitments
+#[derive(Clone, Default, PartialEq)]
+pub struct Commitment(
+    /// signer identifier
+    pub u8,
+    /// hiding nonce commitment
+    pub Vec<u8>,
+    /// binding nonce commitment
+    pub Vec<u8>,
+);
+
+impl From<Commitment> for Vec<u8> {
+    fn from(val: Commitment) -> Self {
+        let mut v = Vec::default();
+        // add in the signer identifier
+        v.append(&mut Varuint(val.0).into());
+        // add in the hiding nonce commitment
+        v.append(&mut Varbytes(val.1.clone()).into());
+        // add in the binding nonce commitment
+        v.append(&mut Varbytes(val.2.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for Commitment {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signer identifier
+        let (id, ptr) = Varuint::<u8>::try_decode_from(bytes)?;
+        // try to decode the hiding nonce commitment
+        let (hiding, ptr) = Varbytes::try_decode_from(ptr)?;
+        // try to decode the binding nonce commitment
+        let (binding, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((
+            Self(id.to_inner(), hiding.to_inner(), binding.to_inner()),
+            ptr,
+        ))
+    }
+}
+
+/// tuple of the group verifying key and the commitment list sent to the
+/// signers in round two. every signature share in a signing session is
+/// computed over the same signing package.
+#[derive(Clone, Default, PartialEq)]
+pub struct SigningPackage(
+    /// group verifying key
+    pub Vec<u8>,
+    /// commitment list, sorted by signer identifier
+    pub Vec<Commitment>,
+);
+
+impl From<SigningPackage> for Vec<u8> {
+    fn from(val: SigningPackage) -> Self {
+        let mut v = Vec::default();
+        // add in the group verifying key
+        v.append(&mut Varbytes(val.0.clone()).into());
+        // add in the number of commitments
+        v.append(&mut Varuint(val.1.len()).into());
+        // add in the commitments
+        val.1.iter().for_each(|c| {
+            v.append(&mut c.clone().into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigningPackage {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (package, _) = Self::try_decode_from(bytes)?;
+        Ok(package)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigningPackage {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the group verifying key
+        let (group_key, ptr) = Varbytes::try_decode_from(bytes)?;
+        // try to decode the number of commitments
+        let (num_commitments, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        if *num_commitments > MAX_SIGNERS {
+            return Err(SharesError::TooManyShares.into());
+        }
+        // try to decode the commitments
+        let mut commitments: Vec<Commitment> = Vec::with_capacity(*num_commitments);
+        let mut p = ptr;
+        for _ in 0..*num_commitments {
+            let (c, ptr) = Commitment::try_decode_from(p)?;
+            // the commitment list is sorted by unique, non-zero identifiers
+            let last = commitments.last().map(|l| l.0).unwrap_or_default();
+            if c.0 <= last {
+                return Err(SharesError::InvalidShareIdentifier(c.0).into());
+            }
+            commitments.push(c);
+            p = ptr;
+        }
+        Ok((Self(group_key.to_inner(), commitments), p))
+    }
+}
+
+/// tuple of signature share data with threshold attributes
+#[derive(Clone)]
+pub struct SigShare(
+    /// identifier
+    pub u8,
+    /// threshold
+    pub usize,
+    /// limit
+    pub usize,
+    /// share bytes
+    pub Vec<u8>,
+);
+
+impl From<SigShare> for Vec<u8> {
+    fn from(val: SigShare) -> Self {
+        let mut v = Vec::default();
+        // add in the share identifier
+        v.append(&mut Varuint(val.0).into());
+        // add in the share threshold
+        v.append(&mut Varuint(val.1).into());
+        // add in the share limit
+        v.append(&mut Varuint(val.2).into());
+        // add in the share data
+        v.append(&mut Varbytes(val.3.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigShare {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the identifier
+        let (id, ptr) = Varuint::<u8>::try_decode_from(bytes)?;
+        // try to decode the threshold
+        let (threshold, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        // try to decode the limit
+        let (limit, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        // try to decode the share data
+        let (share_data, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((
+            Self(
+                id.to_inner(),
+                threshold.to_inner(),
+                limit.to_inner(),
+                share_data.to_inner(),
+            ),
+            ptr,
+        ))
+    }
+}
+
+/// tuple of a signature share and the signing package it was computed over
+#[derive(Clone)]
+pub struct SigSharePackage(
+    /// signature share
+    pub SigShare,
+    /// signing package
+    pub SigningPackage,
+);
+
+impl From<SigSharePackage> for Vec<u8> {
+    fn from(val: SigSharePackage) -> Self {
+        let mut v = Vec::default();
+        // add in the signature share
+        v.append(&mut val.0.into());
+        // add in the signing package
+        v.append(&mut val.1.into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigSharePackage {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (share, _) = Self::try_decode_from(bytes)?;
+        Ok(share)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigSharePackage {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signature share
+        let (share, ptr) = SigShare::try_decode_from(bytes)?;
+        // try to decode the signing package
+        let (package, ptr) = SigningPackage::try_decode_from(ptr)?;
+        Ok((Self(share, package), ptr))
+    }
+}
+
+/// the signing package and the signature shares accumulated so far
+#[derive(Clone, Default)]
+pub(crate) struct ThresholdData(pub(crate) SigningPackage, pub(crate) BTreeMap<u8, SigShare>);
+
+impl From<ThresholdData> for Vec<u8> {
+    fn from(val: ThresholdData) -> Self {
+        let mut v = Vec::default();
+        // add in the signing package
+        v.append(&mut val.0.into());
+        // add in the number of sig shares
+        v.append(&mut Varuint(val.1.len()).into());
+        // add in the sig shares
+        val.1.iter().for_each(|(_, share)| {
+            v.append(&mut share.clone().into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ThresholdData {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (tdata, _) = Self::try_decode_from(bytes)?;
+        Ok(tdata)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for ThresholdData {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signing package
+        let (package, ptr) = SigningPackage::try_decode_from(bytes)?;
+        // try to decode the number of shares
+        let (num_shares, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        if *num_shares > MAX_SIGNERS {
+            return Err(SharesError::TooManyShares.into());
+        }
+        // try to decode the shares
+        let mut shares = BTreeMap::new();
+        let mut p = ptr;
+        for _ in 0..*num_shares {
+            let (share, ptr) = SigShare::try_decode_from(p)?;
+            shares.insert(share.0, share);
+            p = ptr;
+        }
+        Ok((Self(package, shares), p))
+    }
+}
+
+/// the FROST(Ed25519, SHA-512) hash functions (RFC 9591 6.1)
+fn ed25519_hash(tag: &[u8], m: &[u8]) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    hasher.update(Ciphersuite::Ed25519.context());
+    hasher.update(tag);
+    hasher.update(m);
+    hasher.finalize().into()
+}
+
+/// deserialize a canonically encoded, prime order Ed25519 element
+fn ed25519_element(bytes: &[u8]) -> Result<EdwardsPoint, Error> {
+    let err = || SharesError::ShareCombineFailed("invalid Ed25519 element".to_string());
+    let b: [u8; 32] = bytes.try_into().map_err(|_| err())?;
+    match CompressedEdwardsY(b).decompress() {
+        Some(p) if !p.is_identity() && p.is_torsion_free() && p.compress().0 == b => Ok(p),
+        _ => Err(err().into()),
+    }
+}
+
+/// deserialize a canonically encoded Ed25519 scalar
+fn ed25519_scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+    let err = || SharesError::ShareCombineFailed("invalid Ed25519 scalar".to_string());
+    let b: [u8; 32] = bytes.try_into().map_err(|_| err())?;
+    Option::<Scalar>::from(Scalar::from_canonical_bytes(b)).ok_or(err().into())
+}
+
+/// compute each signer's binding factor for the signing package and message
+/// (RFC 9591 4.4)
+pub(crate) fn ed25519_binding_factors(package: &SigningPackage, msg: &[u8]) -> Vec<Scalar> {
+    // encode the group commitment list
+    let mut encoded = Vec::default();
+    package.1.iter().for_each(|c| {
+        encoded.extend_from_slice(Scalar::from(c.0).as_bytes());
+        encoded.extend_from_slice(&c.1);
+        encoded.extend_from_slice(&c.2);
+    });
+    let mut prefix = package.0.clone();
+    prefix.extend_from_slice(&ed25519_hash(b"msg", msg));
+    prefix.extend_from_slice(&ed25519_hash(b"com", &encoded));
+
+    package
+        .1
+        .iter()
+        .map(|c| {
+            let mut rho_input = prefix.clone();
+            rho_input.extend_from_slice(Scalar::from(c.0).as_bytes());
+            Scalar::from_bytes_mod_order_wide(&ed25519_hash(b"rho", &rho_input))
+        })
+        .collect()
+}
+
+/// compute the group commitment for the signing package and message
+/// (RFC 9591 4.5)
+pub(crate) fn ed25519_group_commitment(
+    package: &SigningPackage,
+    msg: &[u8],
+) -> Result<EdwardsPoint, Error> {
+    // sum each signer's hiding commitment and binding commitment scaled by
+    // their binding factor
+    let mut r = EdwardsPoint::identity();
+    for (c, rho) in package.1.iter().zip(ed25519_binding_factors(package, msg)) {
+        r += ed25519_element(&c.1)? + ed25519_element(&c.2)? * rho;
+    }
+    Ok(r)
+}
+
+/// compute the Ed25519 challenge (RFC 8032 5.1.6)
+pub(crate) fn ed25519_challenge(r: &EdwardsPoint, group_key: &EdwardsPoint, msg: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(r.compress().as_bytes());
+    hasher.update(group_key.compress().as_bytes());
+    hasher.update(msg);
+    Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+}
+
+/// aggregate the signature shares from every signer in the signing package
+/// into an Ed25519 signature (RFC 9591 5.3)
+fn ed25519_aggregate(
+    package: &SigningPackage,
+    shares: &BTreeMap<u8, SigShare>,
+    msg: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let group_key = ed25519_element(&package.0)?;
+    let r = ed25519_group_commitment(package, msg)?;
+    let mut z = Scalar::ZERO;
+    for c in &package.1 {
+        let share = shares.get(&c.0).ok_or_else(|| {
+            SharesError::ShareCombineFailed(format!("missing signature share {}", c.0))
+        })?;
+        z += ed25519_scalar(&share.3)?;
+    }
+    // catch bad shares here instead of handing out a signature that no
+    // verifier will accept
+    let c = ed25519_challenge(&r, &group_key, msg);
+    if EdwardsPoint::mul_base(&z) != r + group_key * c {
+        return Err(SharesError::ShareCombineFailed(
+            "aggregate signature does not verify".to_string(),
+        )
+        .into());
+    }
+    let mut sig = r.compress().as_bytes().to_vec();
+    sig.extend_from_slice(z.as_bytes());
+    Ok(sig)
+}
+
+/// the FROST(secp256k1, SHA-256) H4 and H5 hash functions (RFC 9591 6.5)
+fn secp256k1_hash(cs: Ciphersuite, tag: &[u8], m: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(cs.context());
+    hasher.update(tag);
+    hasher.update(m);
+    hasher.finalize().into()
+}
+
+/// the FROST(secp256k1, SHA-256) H1 and H2 hash functions (RFC 9591 6.5)
+fn secp256k1_hash_to_scalar(cs: Ciphersuite, tag: &[u8], m: &[u8]) -> k256::Scalar {
+    let mut out = [k256::Scalar::ZERO];
+    // expanding to a single 48 byte scalar cannot fail
+    let _ =
+        hash_to_field::<ExpandMsgXmd<Sha256>, k256::Scalar>(&[m], &[cs.context(), tag], &mut out);
+    out[0]
+}
+
+/// deserialize a compressed SEC1 encoded secp256k1 element
+fn secp256k1_element(bytes: &[u8]) -> Result<k256::ProjectivePoint, Error> {
+    let err = || SharesError::ShareCombineFailed("invalid secp256k1 element".to_string());
+    let b: [u8; 33] = bytes.try_into().map_err(|_| err())?;
+    Option::<k256::AffinePoint>::from(k256::AffinePoint::from_bytes(&b.into()))
+        .map(k256::ProjectivePoint::from)
+        .filter(|p| *p != k256::ProjectivePoint::IDENTITY)
+        .ok_or_else(|| err().into())
+}
+
+/// deserialize a canonically encoded secp256k1 scalar
+fn secp256k1_scalar(bytes: &[u8]) -> Result<k256::Scalar, Error> {
+    let err = || SharesError::ShareCombineFailed("invalid secp256k1 scalar".to_string());
+    let b: [u8; 32] = bytes.try_into().map_err(|_| err())?;
+    Option::<k256::Scalar>::from(k256::Scalar::from_repr(b.into())).ok_or_else(|| err().into())
+}
+
+/// compute each signer's binding factor for the signing package and message
+/// (RFC 9591 4.4)
+pub(crate) fn secp256k1_binding_factors(
+    cs: Ciphersuite,
+    package: &SigningPackage,
+    msg: &[u8],
+) -> Vec<k256::Scalar> {
+    // encode the group commitment list
+    let mut encoded = Vec::default();
+    package.1.iter().for_each(|c| {
+        encoded.extend_from_slice(&k256::Scalar::from(c.0 as u64).to_bytes());
+        encoded.extend_from_slice(&c.1);
+        encoded.extend_from_slice(&c.2);
+    });
+    let mut prefix = package.0.clone();
+    prefix.extend_from_slice(&secp256k1_hash(cs, b"msg", msg));
+    prefix.extend_from_slice(&secp256k1_hash(cs, b"com", &encoded));
+
+    package
+        .1
+        .iter()
+        .map(|c| {
+            let mut rho_input = prefix.clone();
+            rho_input.extend_from_slice(&k256::Scalar::from(c.0 as u64).to_bytes());
+            secp256k1_hash_to_scalar(cs, b"rho", &rho_input)
+        })
+        .collect()
+}
+
+/// compute the group commitment for the signing package and message
+/// (RFC 9591 4.5)
+pub(crate) fn secp256k1_group_commitment(
+    cs: Ciphersuite,
+    package: &SigningPackage,
+    msg: &[u8],
+) -> Result<k256::ProjectivePoint, Error> {
+    // sum each signer's hiding commitment and binding commitment scaled by
+    // their binding factor
+    let mut r = k256::ProjectivePoint::IDENTITY;
+    for (c, rho) in package
+        .1
+        .iter()
+        .zip(secp256k1_binding_factors(cs, package, msg))
+    {
+        r += secp256k1_element(&c.1)? + secp256k1_element(&c.2)? * rho;
+    }
+    Ok(r)
+}
+
+/// negate the point if it has an odd y coordinate, BIP-340 signatures are
+/// verified against the even y point with the same x coordinate
+pub(crate) fn secp256k1_even_y(p: k256::ProjectivePoint) -> k256::ProjectivePoint {
+    if p.to_affine().y_is_odd().into() {
+        -p
+    } else {
+        p
+    }
+}
+
+/// compute the challenge, the BIP-340 compatible variant uses the BIP-340
+/// tagged challenge hash over the x coordinates
+pub(crate) fn secp256k1_challenge(
+    cs: Ciphersuite,
+    r: &k256::ProjectivePoint,
+    group_key: &k256::ProjectivePoint,
+    msg: &[u8],
+) -> k256::Scalar {
+    match cs {
+        Ciphersuite::Secp256k1Tr => {
+            let tag = Sha256::digest(b"BIP0340/challenge");
+            let hash = Sha256::new()
+                .chain_update(tag)
+                .chain_update(tag)
+                .chain_update(r.to_affine().x())
+                .chain_update(group_key.to_affine().x())
+                .chain_update(msg)
+                .finalize();
+            <k256::Scalar as Reduce<k256::U256>>::reduce_bytes(&hash)
+        }
+        _ => {
+            let mut m = r.to_affine().to_bytes().to_vec();
+            m.extend_from_slice(&group_key.to_affine().to_bytes());
+            m.extend_from_slice(msg);
+            secp256k1_hash_to_scalar(cs, b"chal", &m)
+        }
+    }
+}
+
+/// aggregate the signature shares from every signer in the signing package
+/// into a FROST Schnorr signature (RFC 9591 5.3) or a BIP-340 signature
+fn secp256k1_aggregate(
+    cs: Ciphersuite,
+    package: &SigningPackage,
+    shares: &BTreeMap<u8, SigShare>,
+    msg: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let group_key = secp256k1_element(&package.0)?;
+    let r = secp256k1_group_commitment(cs, package, msg)?;
+    let mut z = k256::Scalar::ZERO;
+    for c in &package.1 {
+        let share = shares.get(&c.0).ok_or_else(|| {
+            SharesError::ShareCombineFailed(format!("missing signature share {}", c.0))
+        })?;
+        z += secp256k1_scalar(&share.3)?;
+    }
+    // catch bad shares here instead of handing out a signature that no
+    // verifier will accept
+    let c = secp256k1_challenge(cs, &r, &group_key, msg);
+    let (r_check, key_check) = match cs {
+        Ciphersuite::Secp256k1Tr => (secp256k1_even_y(r), secp256k1_even_y(group_key)),
+        _ => (r, group_key),
+    };
+    if k256::ProjectivePoint::GENERATOR * z != r_check + key_check * c {
+        return Err(SharesError::ShareCombineFailed(
+            "aggregate signature does not verify".to_string(),
+        )
+        .into());
+    }
+    let mut sig = match cs {
+        Ciphersuite::Secp256k1Tr => r.to_affine().x().to_vec(),
+        _ => r.to_affine().to_bytes().to_vec(),
+    };
+    sig.extend_from_slice(&z.to_bytes());
+    Ok(sig)
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl View<'_> {
+    /// get the FROST ciphersuite and whether this Multisig is a signature
+    /// share. Multisigs accumulating shares record the scheme of the
+    /// signature the shares combine into.
+    fn ciphersuite(&self) -> Result<(Ciphersuite, bool), Error> {
+        match self.ms.codec {
+            Codec::EddsaMsig => match eddsa::SchemeTypeId::try_from(self.ms)? {
+                eddsa::SchemeTypeId::Ed25519 => Ok((Ciphersuite::Ed25519, false)),
+                eddsa::SchemeTypeId::FrostShare => Ok((Ciphersuite::Ed25519, true)),
+                scheme => Err(Error::UnsupportedAlgorithm(scheme.to_string())),
+            },
+            Codec::Es256KMsig => match secp256k1::SchemeTypeId::try_from(self.ms)? {
+                secp256k1::SchemeTypeId::Frost => Ok((Ciphersuite::Secp256k1, false)),
+                secp256k1::SchemeTypeId::FrostShare => Ok((Ciphersuite::Secp256k1, true)),
+                secp256k1::SchemeTypeId::Schnorr => Ok((Ciphersuite::Secp256k1Tr, false)),
+                secp256k1::SchemeTypeId::FrostTaprootShare => Ok((Ciphersuite::Secp256k1Tr, true)),
+                scheme => Err(Error::UnsupportedAlgorithm(scheme.to_string())),
+            },
+            _ => Err(Error::UnsupportedAlgorithm(self.ms.codec.to_string())),
+        }
+    }
+
+    /// true if this Multisig is a FROST signature share
+    fn is_share(&self) -> Result<bool, Error> {
+        Ok(self.ciphersuite()?.1)
+    }
+
+    /// check that this Multisig accumulates FROST signature shares
+    fn check_accumulator(&self) -> Result<Ciphersuite, Error> {
+        match self.ciphersuite()? {
+            (_, true) => Err(SharesError::IsASignatureShare.into()),
+            (cs, false) => Ok(cs),
+        }
+    }
+
+    /// get the scheme of the signature the shares combine into
+    fn combined_scheme(&self) -> Result<Option<u8>, Error> {
+        match self.ms.attributes.get(&AttrId::Scheme) {
+            Some(v) => Ok(Some(Varuint::<u8>::try_from(v.as_slice())?.to_inner())),
+            None => Ok(None),
+        }
+    }
+
+    /// get the threshold data accumulated so far
+    fn accumulated(&self) -> ThresholdData {
+        match self.ms.attributes.get(&AttrId::ThresholdData) {
+            Some(b) => ThresholdData::try_from(b.as_slice()).unwrap_or_default(),
+            None => ThresholdData::default(),
+        }
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for FROST Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// FROST signature shares are identified by their scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        match self.ms.codec {
+            Codec::Es256KMsig => Ok(secp256k1::SchemeTypeId::try_from(self.ms)?.code()),
+            _ => Ok(eddsa::SchemeTypeId::try_from(self.ms)?.code()),
+        }
+    }
+}
+
+impl DataView for View<'_> {
+    /// For FROST signature shares, the round two signature share is stored
+    /// using the AttrId::SigData attribute id.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        let (cs, is_share) = self.ciphersuite()?;
+        if is_share && sig.len() != cs.share_size() {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        let (cs, is_share) = self.ciphersuite()?;
+        if !is_share {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // get the threshold attributes
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold()?;
+        let limit = av.limit()?;
+        let identifier = av.identifier()?;
+        let package = SigningPackage::try_from(av.threshold_data()?)?;
+
+        // create the sig share tuple
+        let sig_data: Vec<u8> =
+            SigSharePackage(SigShare(identifier, threshold, limit, sig_bytes), package).into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(cs.algorithm_name())
+                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
+
+impl ThresholdAttrView for View<'_> {
+    /// get the threshold value for this multisig
+    fn threshold(&self) -> Result<usize, Error> {
+        let threshold = self
+            .ms
+            .attributes
+            .get(&AttrId::Threshold)
+            .ok_or(AttributesError::MissingThreshold)?;
+        Ok(Varuint::<usize>::try_from(threshold.as_slice())?.to_inner())
+    }
+    /// get the limit value for this multisig
+    fn limit(&self) -> Result<usize, Error> {
+        let limit = self
+            .ms
+            .attributes
+            .get(&AttrId::Limit)
+            .ok_or(AttributesError::MissingLimit)?;
+        Ok(Varuint::<usize>::try_from(limit.as_slice())?.to_inner())
+    }
+    /// get the share identifier
+    fn identifier(&self) -> Result<u8, Error> {
+        if !self.is_share()? {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+        let identifier = self
+            .ms
+            .attributes
+            .get(&AttrId::ShareIdentifier)
+            .ok_or(AttributesError::MissingIdentifier)?;
+        Ok(Varuint::<u8>::try_from(identifier.as_slice())?.to_inner())
+    }
+    /// get the threshold data. for signature shares this is the signing
+    /// package, for the accumulating Multisig it is the signing package and
+    /// the shares added so far.
+    fn threshold_data(&self) -> Result<&[u8], Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::ThresholdData)
+            .ok_or(AttributesError::MissingThresholdData)?;
+        Ok(v.as_slice())
+    }
+}
+
+/// trait for accumulating shares to aggregate a FROST signature
+impl ThresholdView for View<'_> {
+    /// get the signature shares
+    fn shares(&self) -> Result<Vec<Multisig>, Error> {
+        let cs = self.check_accumulator()?;
+
+        let threshold_data = self.accumulated();
+        let package: Vec<u8> = threshold_data.0.into();
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            av.payload_encoding().ok()
+        };
+
+        // build multisigs out of each share, preserve the message and the
+        // payload encoding value
+        threshold_data
+            .1
+            .values()
+            .map(|share| {
+                let builder = Builder::new(self.ms.codec)
+                    .with_message_bytes(&self.ms.message.as_slice())
+                    .with_scheme(cs.share_scheme())
+                    .with_identifier(share.0)
+                    .with_threshold(share.1)
+                    .with_limit(share.2)
+                    .with_signature_bytes(&share.3)
+                    .with_threshold_data(&package);
+                if let Some(encoding) = encoding {
+                    builder.with_payload_encoding(encoding).try_build()
+                } else {
+                    builder.try_build()
+                }
+            })
+            .collect()
+    }
+    /// add a new share and return the Multisig with the share added
+    fn add_share(&self, share: &Multisig) -> Result<Multisig, Error> {
+        let cs = self.check_accumulator()?;
+
+        // the share must be a FROST signature share for the same ciphersuite
+        if share.codec != self.ms.codec {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+        match View::try_from(share)?.ciphersuite()? {
+            (_, false) => return Err(SharesError::NotASignatureShare.into()),
+            (share_cs, true) if share_cs != cs => return Err(SharesError::ShareTypeMismatch.into()),
+            _ => {}
+        }
+
+        let (sdata, package, encoding) = {
+            // get the share's attributes
+            let av = share.threshold_attr_view()?;
+            let threshold = av.threshold()?;
+            let limit = av.limit()?;
+            let identifier = av.identifier()?;
+            let package = SigningPackage::try_from(av.threshold_data()?)?;
+
+            // get the share's signature data
+            let dv = share.data_view()?;
+            let sig_bytes = dv.sig_bytes()?;
+
+            let encoding = {
+                let av = share.attr_view()?;
+                av.payload_encoding().ok()
+            };
+
+            (
+                SigShare(identifier, threshold, limit, sig_bytes),
+                package,
+                encoding,
+            )
+        };
+
+        // update the threshold data
+        let ThresholdData(mut signing_package, mut shares) = self.accumulated();
+        let SigningPackage(_, commitments) = &signing_package;
+        if commitments.is_empty() {
+            // adopt the signing package from the first share added
+            signing_package = package;
+        } else if signing_package != package {
+            return Err(SharesError::SigningPackageMismatch.into());
+        }
+        // only signers in the commitment list can contribute shares
+        let SigningPackage(_, commitments) = &signing_package;
+        if !commitments.iter().any(|c| c.0 == sdata.0) {
+            return Err(SharesError::InvalidShareIdentifier(sdata.0).into());
+        }
+        let (threshold, limit) = (sdata.1, sdata.2);
+        shares.insert(sdata.0, sdata);
+        let threshold_data: Vec<u8> = ThresholdData(signing_package, shares).into();
+
+        // get the payload encoding
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            // if this multisig doesn't have payload encoding set, set it to
+            // the value from the first share added
+            match av.payload_encoding() {
+                Ok(encoding) => Some(encoding),
+                Err(_) => encoding,
+            }
+        };
+
+        // if this multisig doesn't already have the threshold/limit set then
+        // set it to match the values from the first share added
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold().unwrap_or(threshold);
+        let limit = av.limit().unwrap_or(limit);
+
+        let mut builder = Builder::new(self.ms.codec)
+            .with_message_bytes(&self.ms.message.as_slice())
+            .with_threshold(threshold)
+            .with_limit(limit)
+            .with_threshold_data(&threshold_data);
+        // keep the scheme of the signature the shares combine into
+        if let Some(scheme) = self.combined_scheme()? {
+            builder = builder.with_scheme(scheme);
+        }
+
+        if let Some(encoding) = encoding {
+            builder.with_payload_encoding(encoding).try_build()
+        } else {
+            builder.try_build()
+        }
+    }
+    /// aggregate the signature shares. FROST signatures are computed over
+    /// the message so it must be attached to this Multisig.
+    fn combine(&self) -> Result<Multisig, Error> {
+        let cs = self.check_accumulator()?;
+
+        let ThresholdData(signing_package, shares) = self.accumulated();
+
+        // check that we have enough shares to combine
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold()?;
+        if shares.len() < threshold || signing_package.1.len() < threshold {
+            return Err(SharesError::NotEnoughShares.into());
+        }
+
+        let sig_bytes = cs.aggregate(&signing_package, &shares, &self.ms.message)?;
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            av.payload_encoding().ok()
+        };
+        let mut builder = Builder::new(self.ms.codec)
+            .with_message_bytes(&self.ms.message.as_slice())
+            .with_signature_bytes(&sig_bytes);
+        if let Some(scheme) = self.combined_scheme()? {
+            builder = builder.with_scheme(scheme);
+        }
+
+        if let Some(encoding) = encoding {
+            builder.with_payload_encoding(encoding).try_build()
+        } else {
+            builder.try_build()
+        }
+    }
+}
@@ -0,0 +1,704 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError, SharesError},
+    views::secp256k1,
+    AttrId, AttrView, Builder, ConvView, DataView, Error, Multisig, ThresholdAttrView,
+    ThresholdView, Views,
+};
+use k256::{
+    elliptic_curve::{group::GroupEncoding, ops::Reduce, point::AffineCoordinates, PrimeField},
+    AffinePoint, ProjectivePoint, Scalar, U256,
+};
+use multicodec::Codec;
+use multitrait::TryDecodeFrom;
+use multiutil::{Varbytes, Varuint};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// the name used to identify MuSig2 partial signatures in non-Multikey
+/// formats
+pub const ALGORITHM_NAME_PARTIAL: &str = "secp256k1-musig2-partial@multisig";
+
+/// the size of a MuSig2 partial signature in bytes
+pub const PARTIAL_SIG_SIZE: usize = 32;
+/// the size of a MuSig2 public nonce in bytes
+pub const PUBLIC_NONCE_SIZE: usize = 66;
+/// the size of a compressed secp256k1 public key in bytes
+pub const PUBLIC_KEY_SIZE: usize = 33;
+/// the largest number of signers, signers are identified by their one-based
+/// position in the key list
+pub const MAX_SIGNERS: usize = u8::MAX as usize;
+
+/// The ordered list of compressed signer public keys that are aggregated into
+/// the MuSig2 aggregate public key (BIP-327). The signer identifier is the
+/// one-based position of the signer's public key in the list.
+#[derive(Clone, Default, PartialEq)]
+pub struct KeyList(
+    /// compressed signer public keys
+    pub Vec<Vec<u8>>,
+);
+
+impl From<KeyList> for Vec<u8> {
+    fn from(val: KeyList) -> Self {
+        let mut v = Vec::default();
+        // add in the number of public keys
+        v.append(&mut Varuint(val.0.len()).into());
+        // add in the public keys
+        val.0.iter().for_each(|pk| {
+            v.append(&mut Varbytes(pk.clone()).into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for KeyList {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (keys, _) = Self::try_decode_from(bytes)?;
+        Ok(keys)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for KeyList {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the number of public keys
+        let (num_keys, ptr) = Varuint::<usize>::try_decode_from(bytes)?;
+        if *num_keys > MAX_SIGNERS {
+            return Err(SharesError::TooManyShares.into());
+        }
+        // try to decode the public keys
+        let mut keys = Vec::with_capacity(*num_keys);
+        let mut p = ptr;
+        for _ in 0..*num_keys {
+            let (pk, ptr) = Varbytes::try_decode_from(p)?;
+            keys.push(pk.to_inner());
+            p = ptr;
+        }
+        Ok((Self(keys), p))
+    }
+}
+
+/// tuple of partial signature data with threshold attributes
+#[derive(Clone)]
+pub struct PartialSig(
+    /// signer identifier
+    pub u8,
+    /// threshold
+    pub usize,
+    /// limit
+    pub usize,
+    /// public nonce
+    pub Vec<u8>,
+    /// partial signature bytes
+    pub Vec<u8>,
+);
+
+impl From<PartialSig> for Vec<u8> {
+    fn from(val: PartialSig) -> Self {
+        let mut v = Vec::default();
+        // add in the signer identifier
+        v.append(&mut Varuint(val.0).into());
+        // add in the threshold
+        v.append(&mut Varuint(val.1).into());
+        // add in the limit
+        v.append(&mut Varuint(val.2).into());
+        // add in the public nonce
+        v.append(&mut Varbytes(val.3.clone()).into());
+        // add in the partial signature
+        v.append(&mut Varbytes(val.4.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for PartialSig {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signer identifier
+        let (id, ptr) = Varuint::<u8>::try_decode_from(bytes)?;
+        // try to decode the threshold
+        let (threshold, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        // try to decode the limit
+        let (limit, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        // try to decode the public nonce
+        let (pubnonce, ptr) = Varbytes::try_decode_from(ptr)?;
+        // try to decode the partial signature
+        let (psig, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((
+            Self(
+                id.to_inner(),
+                threshold.to_inner(),
+                limit.to_inner(),
+                pubnonce.to_inner(),
+                psig.to_inner(),
+            ),
+            ptr,
+        ))
+    }
+}
+
+/// tuple of a partial signature and the key list it was computed for
+#[derive(Clone)]
+pub struct PartialSigPackage(
+    /// partial signature
+    pub PartialSig,
+    /// signer public keys
+    pub KeyList,
+);
+
+impl From<PartialSigPackage> for Vec<u8> {
+    fn from(val: PartialSigPackage) -> Self {
+        let mut v = Vec::default();
+        // add in the partial signature
+        v.append(&mut val.0.into());
+        // add in the key list
+        v.append(&mut val.1.into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for PartialSigPackage {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (psig, _) = Self::try_decode_from(bytes)?;
+        Ok(psig)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for PartialSigPackage {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the partial signature
+        let (psig, ptr) = PartialSig::try_decode_from(bytes)?;
+        // try to decode the key list
+        let (keys, ptr) = KeyList::try_decode_from(ptr)?;
+        Ok((Self(psig, keys), ptr))
+    }
+}
+
+/// the key list and the partial signatures accumulated so far
+#[derive(Clone, Default)]
+pub(crate) struct ThresholdData(pub(crate) KeyList, pub(crate) BTreeMap<u8, PartialSig>);
+
+impl From<ThresholdData> for Vec<u8> {
+    fn from(val: ThresholdData) -> Self {
+        let mut v = Vec::default();
+        // add in the key list
+        v.append(&mut val.0.into());
+        // add in the number of partial signatures
+        v.append(&mut Varuint(val.1.len()).into());
+        // add in the partial signatures
+        val.1.iter().for_each(|(_, psig)| {
+            v.append(&mut psig.clone().into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ThresholdData {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (tdata, _) = Self::try_decode_from(bytes)?;
+        Ok(tdata)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for ThresholdData {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the key list
+        let (keys, ptr) = KeyList::try_decode_from(bytes)?;
+        // try to decode the number of partial signatures
+        let (num_psigs, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        if *num_psigs > MAX_SIGNERS {
+            return Err(SharesError::TooManyShares.into());
+        }
+        // try to decode the partial signatures
+        let mut psigs = BTreeMap::new();
+        let mut p = ptr;
+        for _ in 0..*num_psigs {
+            let (psig, ptr) = PartialSig::try_decode_from(p)?;
+            psigs.insert(psig.0, psig);
+            p = ptr;
+        }
+        Ok((Self(keys, psigs), p))
+    }
+}
+
+/// the BIP-340 tagged hash reduced to a scalar
+fn tagged_hash(tag: &[u8], parts: &[&[u8]]) -> Scalar {
+    let tag = Sha256::digest(tag);
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    parts.iter().for_each(|p| hasher.update(p));
+    <Scalar as Reduce<U256>>::reduce_bytes(&hasher.finalize())
+}
+
+/// deserialize a compressed SEC1 encoded point
+fn point(bytes: &[u8]) -> Result<ProjectivePoint, Error> {
+    let err = || SharesError::ShareCombineFailed("invalid secp256k1 point".to_string());
+    let b: [u8; PUBLIC_KEY_SIZE] = bytes.try_into().map_err(|_| err())?;
+    Option::<AffinePoint>::from(AffinePoint::from_bytes(&b.into()))
+        .map(ProjectivePoint::from)
+        .filter(|p| *p != ProjectivePoint::IDENTITY)
+        .ok_or_else(|| err().into())
+}
+
+/// deserialize a canonically encoded scalar
+fn scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+    let err = || SharesError::ShareCombineFailed("invalid secp256k1 scalar".to_string());
+    let b: [u8; 32] = bytes.try_into().map_err(|_| err())?;
+    Option::<Scalar>::from(Scalar::from_repr(b.into())).ok_or_else(|| err().into())
+}
+
+/// serialize a point as 33 bytes, the point at infinity is all zeros
+fn cbytes_ext(p: &ProjectivePoint) -> Vec<u8> {
+    if *p == ProjectivePoint::IDENTITY {
+        vec![0u8; PUBLIC_KEY_SIZE]
+    } else {
+        p.to_affine().to_bytes().to_vec()
+    }
+}
+
+/// true if the point has an even y coordinate
+pub(crate) fn has_even_y(p: &ProjectivePoint) -> bool {
+    !bool::from(p.to_affine().y_is_odd())
+}
+
+/// aggregate the public keys into the aggregate public key, returning it and
+/// each signer's key aggregation coefficient (BIP-327 KeyAgg)
+pub(crate) fn key_agg(keys: &KeyList) -> Result<(ProjectivePoint, Vec<Scalar>), Error> {
+    let all: Vec<u8> = keys.0.concat();
+    let l = Sha256::digest(b"KeyAgg list");
+    let l = Sha256::new()
+        .chain_update(l)
+        .chain_update(l)
+        .chain_update(&all)
+        .finalize();
+    // the second distinct key gets a coefficient of one
+    let second = keys.0.iter().find(|pk| Some(*pk) != keys.0.first());
+    let mut q = ProjectivePoint::IDENTITY;
+    let mut coefficients = Vec::with_capacity(keys.0.len());
+    for pk in &keys.0 {
+        let a = if Some(pk) == second {
+            Scalar::ONE
+        } else {
+            tagged_hash(b"KeyAgg coefficient", &[&l[..], pk.as_slice()])
+        };
+        q += point(pk)? * a;
+        coefficients.push(a);
+    }
+    if q == ProjectivePoint::IDENTITY {
+        return Err(SharesError::ShareCombineFailed(
+            "aggregate public key is the point at infinity".to_string(),
+        )
+        .into());
+    }
+    Ok((q, coefficients))
+}
+
+/// compute the nonce coefficient, the final nonce and the challenge from the
+/// aggregate public key, every signer's public nonce and the message
+/// (BIP-327 NonceAgg and GetSessionValues)
+pub(crate) fn session_values(
+    q: &ProjectivePoint,
+    pubnonces: &[&[u8]],
+    msg: &[u8],
+) -> Result<(Scalar, ProjectivePoint, Scalar), Error> {
+    let (mut r1, mut r2) = (ProjectivePoint::IDENTITY, ProjectivePoint::IDENTITY);
+    for pubnonce in pubnonces {
+        if pubnonce.len() != PUBLIC_NONCE_SIZE {
+            return Err(SharesError::ShareCombineFailed("invalid public nonce".to_string()).into());
+        }
+        let (n1, n2) = pubnonce.split_at(PUBLIC_KEY_SIZE);
+        r1 += point(n1)?;
+        r2 += point(n2)?;
+    }
+    let mut aggnonce = cbytes_ext(&r1);
+    aggnonce.extend_from_slice(&cbytes_ext(&r2));
+    let qx = q.to_affine().x();
+    let b = tagged_hash(b"MuSig/noncecoef", &[aggnonce.as_slice(), &qx[..], msg]);
+    let r = match r1 + r2 * b {
+        r if r == ProjectivePoint::IDENTITY => ProjectivePoint::GENERATOR,
+        r => r,
+    };
+    let rx = r.to_affine().x();
+    let e = tagged_hash(b"BIP0340/challenge", &[&rx[..], &qx[..], msg]);
+    Ok((b, r, e))
+}
+
+/// verify every partial signature and sum them into a BIP-340 signature
+/// (BIP-327 PartialSigVerify and PartialSigAgg)
+fn aggregate(
+    keys: &KeyList,
+    psigs: &BTreeMap<u8, PartialSig>,
+    msg: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let (q, coefficients) = key_agg(keys)?;
+    // MuSig2 is n-of-n so there must be a partial signature from every signer
+    let psigs = (1..=keys.0.len())
+        .map(|id| {
+            psigs.get(&(id as u8)).ok_or_else(|| {
+                SharesError::ShareCombineFailed(format!("missing partial signature {}", id)).into()
+            })
+        })
+        .collect::<Result<Vec<&PartialSig>, Error>>()?;
+    let pubnonces: Vec<&[u8]> = psigs.iter().map(|p| p.3.as_slice()).collect();
+    let (b, r, e) = session_values(&q, &pubnonces, msg)?;
+
+    // signers negate their nonces if R has an odd y and their keys if the
+    // aggregate public key has an odd y
+    let g = if has_even_y(&q) {
+        Scalar::ONE
+    } else {
+        -Scalar::ONE
+    };
+    let mut s = Scalar::ZERO;
+    for ((psig, pk), a) in psigs.iter().zip(&keys.0).zip(&coefficients) {
+        let s_i = scalar(&psig.4)?;
+        let (n1, n2) = psig.3.split_at(PUBLIC_KEY_SIZE);
+        let re = point(n1)? + point(n2)? * b;
+        let re = if has_even_y(&r) { re } else { -re };
+        // catch bad partial signatures here instead of handing out a
+        // signature that no verifier will accept
+        if ProjectivePoint::GENERATOR * s_i != re + point(pk)? * (e * a * g) {
+            return Err(SharesError::ShareCombineFailed(format!(
+                "partial signature {} does not verify",
+                psig.0
+            ))
+            .into());
+        }
+        s += s_i;
+    }
+    let mut sig = r.to_affine().x().to_vec();
+    sig.extend_from_slice(&s.to_bytes());
+    Ok(sig)
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl View<'_> {
+    /// true if this Multisig is a MuSig2 partial signature
+    fn is_partial(&self) -> Result<bool, Error> {
+        match secp256k1::SchemeTypeId::try_from(self.ms)? {
+            secp256k1::SchemeTypeId::Musig2 => Ok(false),
+            secp256k1::SchemeTypeId::Musig2Partial => Ok(true),
+            scheme => Err(Error::UnsupportedAlgorithm(scheme.to_string())),
+        }
+    }
+
+    /// check that this Multisig accumulates MuSig2 partial signatures
+    fn check_accumulator(&self) -> Result<(), Error> {
+        if self.is_partial()? {
+            return Err(SharesError::IsASignatureShare.into());
+        }
+        Ok(())
+    }
+
+    /// get the threshold data accumulated so far
+    fn accumulated(&self) -> ThresholdData {
+        match self.ms.attributes.get(&AttrId::ThresholdData) {
+            Some(b) => ThresholdData::try_from(b.as_slice()).unwrap_or_default(),
+            None => ThresholdData::default(),
+        }
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for MuSig2 Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// MuSig2 partial signatures are identified by their Es256K scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(secp256k1::SchemeTypeId::try_from(self.ms)?.code())
+    }
+}
+
+impl DataView for View<'_> {
+    /// For MuSig2 partial signatures, the partial signature is stored using
+    /// the AttrId::SigData attribute id.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        if self.is_partial()? && sig.len() != PARTIAL_SIG_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        if !self.is_partial()? {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // get the threshold attributes
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold()?;
+        let limit = av.limit()?;
+        let identifier = av.identifier()?;
+        let pubnonce = av.public_nonce()?.to_vec();
+        let keys = KeyList::try_from(av.threshold_data()?)?;
+
+        // create the partial signature tuple
+        let sig_data: Vec<u8> = PartialSigPackage(
+            PartialSig(identifier, threshold, limit, pubnonce, sig_bytes),
+            keys,
+        )
+        .into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(ALGORITHM_NAME_PARTIAL)
+                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
+
+impl ThresholdAttrView for View<'_> {
+    /// get the threshold value for this multisig, MuSig2 is n-of-n so this
+    /// is the number of signers
+    fn threshold(&self) -> Result<usize, Error> {
+        let threshold = self
+            .ms
+            .attributes
+            .get(&AttrId::Threshold)
+            .ok_or(AttributesError::MissingThreshold)?;
+        Ok(Varuint::<usize>::try_from(threshold.as_slice())?.to_inner())
+    }
+    /// get the limit value for this multisig
+    fn limit(&self) -> Result<usize, Error> {
+        let limit = self
+            .ms
+            .attributes
+            .get(&AttrId::Limit)
+            .ok_or(AttributesError::MissingLimit)?;
+        Ok(Varuint::<usize>::try_from(limit.as_slice())?.to_inner())
+    }
+    /// get the signer identifier, the one-based position of the signer's
+    /// public key in the key list
+    fn identifier(&self) -> Result<u8, Error> {
+        if !self.is_partial()? {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+        let identifier = self
+            .ms
+            .attributes
+            .get(&AttrId::ShareIdentifier)
+            .ok_or(AttributesError::MissingIdentifier)?;
+        Ok(Varuint::<u8>::try_from(identifier.as_slice())?.to_inner())
+    }
+    /// get the threshold data. for partial signatures this is the key list,
+    /// for the accumulating Multisig it is the key list and the partial
+    /// signatures added so far.
+    fn threshold_data(&self) -> Result<&[u8], Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::ThresholdData)
+            .ok_or(AttributesError::MissingThresholdData)?;
+        Ok(v.as_slice())
+    }
+    /// get the signer's public nonce
+    fn public_nonce(&self) -> Result<&[u8], Error> {
+        if !self.is_partial()? {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PublicNonce)
+            .ok_or(AttributesError::MissingPublicNonce)?;
+        if v.len() != PUBLIC_NONCE_SIZE {
+            return Err(AttributesError::InvalidPublicNonceLength(v.len()).into());
+        }
+        Ok(v.as_slice())
+    }
+}
+
+/// trait for accumulating partial signatures to aggregate a MuSig2 signature
+impl ThresholdView for View<'_> {
+    /// get the partial signatures
+    fn shares(&self) -> Result<Vec<Multisig>, Error> {
+        self.check_accumulator()?;
+
+        let threshold_data = self.accumulated();
+        let keys: Vec<u8> = threshold_data.0.into();
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            av.payload_encoding().ok()
+        };
+
+        // build multisigs out of each partial signature, preserve the message
+        // and the payload encoding value
+        threshold_data
+            .1
+            .values()
+            .map(|psig| {
+                let builder = Builder::new(self.ms.codec)
+                    .with_message_bytes(&self.ms.message.as_slice())
+                    .with_scheme(secp256k1::SchemeTypeId::Musig2Partial.into())
+                    .with_identifier(psig.0)
+                    .with_threshold(psig.1)
+                    .with_limit(psig.2)
+                    .with_public_nonce(&psig.3)
+                    .with_signature_bytes(&psig.4)
+                    .with_threshold_data(&keys);
+                if let Some(encoding) = encoding {
+                    builder.with_payload_encoding(encoding).try_build()
+                } else {
+                    builder.try_build()
+                }
+            })
+            .collect()
+    }
+    /// add a new partial signature and return the Multisig with it added
+    fn add_share(&self, share: &Multisig) -> Result<Multisig, Error> {
+        self.check_accumulator()?;
+
+        // the share must be a MuSig2 partial signature
+        if share.codec != self.ms.codec || !View::try_from(share)?.is_partial()? {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+
+        let (psig, keys, encoding) = {
+            // get the partial signature's attributes
+            let av = share.threshold_attr_view()?;
+            let threshold = av.threshold()?;
+            let limit = av.limit()?;
+            let identifier = av.identifier()?;
+            let pubnonce = av.public_nonce()?.to_vec();
+            let keys = KeyList::try_from(av.threshold_data()?)?;
+
+            // get the partial signature data
+            let dv = share.data_view()?;
+            let sig_bytes = dv.sig_bytes()?;
+
+            let encoding = {
+                let av = share.attr_view()?;
+                av.payload_encoding().ok()
+            };
+
+            (
+                PartialSig(identifier, threshold, limit, pubnonce, sig_bytes),
+                keys,
+                encoding,
+            )
+        };
+
+        // update the threshold data
+        let ThresholdData(mut key_list, mut psigs) = self.accumulated();
+        if key_list.0.is_empty() {
+            // adopt the key list from the first partial signature added
+            key_list = keys;
+        } else if key_list != keys {
+            return Err(SharesError::SigningPackageMismatch.into());
+        }
+        // only signers in the key list can contribute partial signatures
+        if psig.0 == 0 || usize::from(psig.0) > key_list.0.len() {
+            return Err(SharesError::InvalidShareIdentifier(psig.0).into());
+        }
+        let (threshold, limit) = (psig.1, psig.2);
+        psigs.insert(psig.0, psig);
+        let threshold_data: Vec<u8> = ThresholdData(key_list, psigs).into();
+
+        // get the payload encoding
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            // if this multisig doesn't have payload encoding set, set it to
+            // the value from the first partial signature added
+            match av.payload_encoding() {
+                Ok(encoding) => Some(encoding),
+                Err(_) => encoding,
+            }
+        };
+
+        // if this multisig doesn't already have the threshold/limit set then
+        // set it to match the values from the first partial signature added
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold().unwrap_or(threshold);
+        let limit = av.limit().unwrap_or(limit);
+
+        let builder = Builder::new(self.ms.codec)
+            .with_message_bytes(&self.ms.message.as_slice())
+            .with_scheme(secp256k1::SchemeTypeId::Musig2.into())
+            .with_threshold(threshold)
+            .with_limit(limit)
+            .with_threshold_data(&threshold_data);
+
+        if let Some(encoding) = encoding {
+            builder.with_payload_encoding(encoding).try_build()
+        } else {
+            builder.try_build()
+        }
+    }
+    /// aggregate the partial signatures into a BIP-340 Schnorr signature.
+    /// MuSig2 signatures are computed over the message so it must be attached
+    /// to this Multisig.
+    fn combine(&self) -> Result<Multisig, Error> {
+        self.check_accumulator()?;
+
+        let ThresholdData(keys, psigs) = self.accumulated();
+
+        // check that we have a partial signature from every signer
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold()?;
+        if keys.0.is_empty() || psigs.len() < threshold.max(keys.0.len()) {
+            return Err(SharesError::NotEnoughShares.into());
+        }
+
+        let sig_bytes = aggregate(&keys, &psigs, &self.ms.message)?;
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            av.payload_encoding().ok()
+        };
+        let builder = Builder::new(self.ms.codec)
+            .with_message_bytes(&self.ms.message.as_slice())
+            .with_scheme(secp256k1::SchemeTypeId::Schnorr.into())
+            .with_signature_bytes(&sig_bytes);
+
+        if let Some(encoding) = encoding {
+            builder.with_payload_encoding(encoding).try_build()
+        } else {
+            builder.try_build()
+        }
+    }
+}
@@ -0,0 +1,180 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    views::ecdsa,
+    AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
+};
+use multicodec::Codec;
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::Varuint;
+use std::fmt;
+
+/// the size of a P-256 scalar in bytes
+pub const SCALAR_SIZE: usize = 32;
+
+/// the P-256 group order, big endian
+pub(crate) const CURVE_ORDER: [u8; SCALAR_SIZE] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+/// The signature schemes carried by the Es256Msig codec. Multisigs without a
+/// scheme attribute are ECDSA signatures.
+#[repr(u8)]
+#[derive(Clone, Copy, Default, Hash, Ord, PartialOrd, PartialEq, Eq)]
+pub enum SchemeTypeId {
+    /// ECDSA with a raw r||s signature
+    #[default]
+    Ecdsa,
+    /// presignature-based threshold ECDSA signature share
+    EcdsaShare,
+}
+
+impl SchemeTypeId {
+    /// Get the code for the scheme
+    pub fn code(&self) -> u8 {
+        (*self).into()
+    }
+
+    /// Convert the scheme to &str
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Ecdsa => "ecdsa",
+            Self::EcdsaShare => "ecdsa-share",
+        }
+    }
+}
+
+impl From<SchemeTypeId> for u8 {
+    fn from(val: SchemeTypeId) -> Self {
+        val as u8
+    }
+}
+
+impl TryFrom<u8> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            0 => Ok(Self::Ecdsa),
+            1 => Ok(Self::EcdsaShare),
+            _ => Err(AttributesError::InvalidScheme(c).into()),
+        }
+    }
+}
+
+impl From<SchemeTypeId> for Vec<u8> {
+    fn from(val: SchemeTypeId) -> Self {
+        val.code().encode_into()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (id, _) = Self::try_decode_from(bytes)?;
+        Ok(id)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (code, ptr) = u8::try_decode_from(bytes)?;
+        Ok((Self::try_from(code)?, ptr))
+    }
+}
+
+impl TryFrom<&str> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "ecdsa" => Ok(Self::Ecdsa),
+            "ecdsa-share" => Ok(Self::EcdsaShare),
+            _ => Err(AttributesError::InvalidSchemeName(s.to_string()).into()),
+        }
+    }
+}
+
+impl TryFrom<&Multisig> for SchemeTypeId {
+    type Error = Error;
+
+    /// get the scheme from the Multisig, defaulting to ECDSA when the scheme
+    /// attribute is absent
+    fn try_from(ms: &Multisig) -> Result<Self, Self::Error> {
+        match ms.attributes.get(&AttrId::Scheme) {
+            Some(v) => Self::try_from(Varuint::<u8>::try_from(v.as_slice())?.to_inner()),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl fmt::Display for SchemeTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for Es256 Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// Es256 signatures are ECDSA if the scheme attribute is absent
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(SchemeTypeId::try_from(self.ms)?.code())
+    }
+}
+
+impl DataView for View<'_> {
+    /// For Es256 Multisig values, the sig data is stored using the
+    /// AttrId::SigData attribute id as the raw r||s signature bytes.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        ecdsa::check_sig_size(sig, SCALAR_SIZE)?;
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+        let sig_data = ecdsa::to_ssh_sig_data(&sig_bytes, SCALAR_SIZE)?;
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Ecdsa {
+                curve: ssh_key::EcdsaCurve::NistP256,
+            },
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    views::ecdsa,
+    AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
+};
+use multicodec::Codec;
+
+/// the size of a P-521 scalar in bytes
+pub const SCALAR_SIZE: usize = 66;
+
+/// the P-521 group order, big endian
+pub(crate) const CURVE_ORDER: [u8; SCALAR_SIZE] = [
+    0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xfa, 0x51, 0x86, 0x87, 0x83, 0xbf, 0x2f, 0x96, 0x6b, 0x7f, 0xcc, 0x01, 0x48, 0xf7, 0x09,
+    0xa5, 0xd0, 0x3b, 0xb5, 0xc9, 0xb8, 0x89, 0x9c, 0x47, 0xae, 0xbb, 0x6f, 0xb7, 0x1e, 0x91, 0x38,
+    0x64, 0x09,
+];
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for Es521 Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// Es521 only has one scheme so this is meaningless
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(0)
+    }
+}
+
+impl DataView for View<'_> {
+    /// For Es521 Multisig values, the sig data is stored using the
+    /// AttrId::SigData attribute id as the raw r||s signature bytes.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        ecdsa::check_sig_size(sig, SCALAR_SIZE)?;
+        // r and s are 521-bit values so only the lowest bit of the most
+        // significant byte of each may be set
+        if sig[0] > 1 || sig[SCALAR_SIZE] > 1 {
+            return Err(AttributesError::InvalidSignature(
+                "P-521 signature component out of range".to_string(),
+            )
+            .into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+        let sig_data = ecdsa::to_ssh_sig_data(&sig_bytes, SCALAR_SIZE)?;
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Ecdsa {
+                curve: ssh_key::EcdsaCurve::NistP521,
+            },
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
@@ -0,0 +1,237 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
+};
+use multicodec::Codec;
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::Varuint;
+use std::fmt;
+
+/// the supported RSA modulus sizes in bytes (2048, 3072 and 4096 bits). every
+/// scheme's encoded message fits in the smallest of them. the modulus size of
+/// the signing key is recorded using the AttrId::ModulusSize attribute id.
+pub const MODULUS_SIZES: [usize; 3] = [256, 384, 512];
+
+/// The RSA signature padding schemes and hash functions
+#[repr(u8)]
+#[derive(Clone, Copy, Default, Hash, Ord, PartialOrd, PartialEq, Eq)]
+pub enum SchemeTypeId {
+    /// RSASSA-PKCS1-v1_5 with SHA2-256
+    #[default]
+    Pkcs1v15Sha256,
+    /// RSASSA-PKCS1-v1_5 with SHA2-384
+    Pkcs1v15Sha384,
+    /// RSASSA-PKCS1-v1_5 with SHA2-512
+    Pkcs1v15Sha512,
+    /// RSASSA-PSS with SHA2-256 and MGF1 with SHA2-256
+    PssSha256,
+    /// RSASSA-PSS with SHA2-384 and MGF1 with SHA2-384
+    PssSha384,
+    /// RSASSA-PSS with SHA2-512 and MGF1 with SHA2-512
+    PssSha512,
+}
+
+impl SchemeTypeId {
+    /// Get the code for the scheme
+    pub fn code(&self) -> u8 {
+        (*self).into()
+    }
+
+    /// Convert the scheme to &str
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Pkcs1v15Sha256 => "pkcs1v15-sha2-256",
+            Self::Pkcs1v15Sha384 => "pkcs1v15-sha2-384",
+            Self::Pkcs1v15Sha512 => "pkcs1v15-sha2-512",
+            Self::PssSha256 => "pss-sha2-256",
+            Self::PssSha384 => "pss-sha2-384",
+            Self::PssSha512 => "pss-sha2-512",
+        }
+    }
+
+    /// Returns true if this is an RSASSA-PSS scheme
+    pub fn is_pss(&self) -> bool {
+        matches!(self, Self::PssSha256 | Self::PssSha384 | Self::PssSha512)
+    }
+
+    /// Get the hash function codec used by the scheme
+    pub fn hash(&self) -> Codec {
+        match self {
+            Self::Pkcs1v15Sha256 | Self::PssSha256 => Codec::Sha2256,
+            Self::Pkcs1v15Sha384 | Self::PssSha384 => Codec::Sha2384,
+            Self::Pkcs1v15Sha512 | Self::PssSha512 => Codec::Sha2512,
+        }
+    }
+}
+
+impl From<SchemeTypeId> for u8 {
+    fn from(val: SchemeTypeId) -> Self {
+        val as u8
+    }
+}
+
+impl TryFrom<u8> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            0 => Ok(Self::Pkcs1v15Sha256),
+            1 => Ok(Self::Pkcs1v15Sha384),
+            2 => Ok(Self::Pkcs1v15Sha512),
+            3 => Ok(Self::PssSha256),
+            4 => Ok(Self::PssSha384),
+            5 => Ok(Self::PssSha512),
+            _ => Err(AttributesError::InvalidScheme(c).into()),
+        }
+    }
+}
+
+impl From<SchemeTypeId> for Vec<u8> {
+    fn from(val: SchemeTypeId) -> Self {
+        val.code().encode_into()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (id, _) = Self::try_decode_from(bytes)?;
+        Ok(id)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (code, ptr) = u8::try_decode_from(bytes)?;
+        Ok((Self::try_from(code)?, ptr))
+    }
+}
+
+impl TryFrom<&str> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "pkcs1v15-sha2-256" => Ok(Self::Pkcs1v15Sha256),
+            "pkcs1v15-sha2-384" => Ok(Self::Pkcs1v15Sha384),
+            "pkcs1v15-sha2-512" => Ok(Self::Pkcs1v15Sha512),
+            "pss-sha2-256" => Ok(Self::PssSha256),
+            "pss-sha2-384" => Ok(Self::PssSha384),
+            "pss-sha2-512" => Ok(Self::PssSha512),
+            _ => Err(AttributesError::InvalidSchemeName(s.to_string()).into()),
+        }
+    }
+}
+
+impl TryFrom<&Multisig> for SchemeTypeId {
+    type Error = Error;
+
+    /// get the RSA scheme from the Multisig, defaulting to PKCS#1 v1.5 with
+    /// SHA2-256 when the scheme attribute is absent
+    fn try_from(ms: &Multisig) -> Result<Self, Self::Error> {
+        match ms.attributes.get(&AttrId::Scheme) {
+            Some(v) => Self::try_from(Varuint::<u8>::try_from(v.as_slice())?.to_inner()),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl fmt::Display for SchemeTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for Rs256 Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// RSA signatures record both the padding scheme and the hash function
+    /// in the scheme, both of which are needed for verifying the signature
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(SchemeTypeId::try_from(self.ms)?.code())
+    }
+}
+
+impl View<'_> {
+    /// get the modulus size of the signing key in bytes, stored using the
+    /// AttrId::ModulusSize attribute id
+    fn modulus_size(&self) -> Result<usize, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::ModulusSize)
+            .ok_or(AttributesError::MissingModulusSize)?;
+        let size = Varuint::<usize>::try_from(v.as_slice())?.to_inner();
+        if !MODULUS_SIZES.contains(&size) {
+            return Err(AttributesError::InvalidModulusSize(size).into());
+        }
+        Ok(size)
+    }
+}
+
+impl DataView for View<'_> {
+    /// For Rs256 Multisig values, the sig data is stored using the
+    /// AttrId::SigData attribute id. the signature is exactly as long as the
+    /// modulus of the signing key.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        SchemeTypeId::try_from(self.ms)?;
+        if sig.len() != self.modulus_size()? {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // get the scheme
+        let scheme_type = SchemeTypeId::try_from(self.ms)?;
+
+        // SSH only supports PKCS#1 v1.5 RSA signatures (RFC 8332)
+        let hash = match scheme_type {
+            SchemeTypeId::Pkcs1v15Sha256 => ssh_key::HashAlg::Sha256,
+            SchemeTypeId::Pkcs1v15Sha512 => ssh_key::HashAlg::Sha512,
+            _ => return Err(Error::UnsupportedAlgorithm(scheme_type.to_string())),
+        };
+
+        Ok(
+            ssh_key::Signature::new(ssh_key::Algorithm::Rsa { hash: Some(hash) }, sig_bytes)
+                .map_err(|e| ConversionsError::Ssh(e.into()))?,
+        )
+    }
+}
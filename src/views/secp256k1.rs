@@ -1,12 +1,203 @@
 // SPDX-License-Idnetifier: Apache-2.0
 use crate::{
     error::{AttributesError, ConversionsError},
-    AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
+    AttrId, AttrView, ConvView, DataView, Error, Multisig, RecoveryView, Views,
 };
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
 use multicodec::Codec;
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::Varuint;
+use std::fmt;
 
 /// the name used to identify these signatures in non-Multikey formats
 pub const ALGORITHM_NAME: &str = "secp256k1@multisig";
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME_SCHNORR: &str = "secp256k1-schnorr@multisig";
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME_FROST: &str = "secp256k1-frost@multisig";
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME_RECOVERABLE: &str = "secp256k1-recoverable@multisig";
+
+/// the size of a secp256k1 scalar in bytes
+pub const SCALAR_SIZE: usize = 32;
+/// the size of a secp256k1 signature in bytes
+pub const SIGNATURE_SIZE: usize = 64;
+/// the size of a FROST(secp256k1, SHA-256) signature in bytes
+pub const FROST_SIGNATURE_SIZE: usize = 65;
+/// the size of a recoverable r||s||v signature in bytes
+pub const RECOVERABLE_SIGNATURE_SIZE: usize = 65;
+
+/// the v value offset for recoverable signatures without a chain id
+const V_OFFSET: u64 = 27;
+/// the v value offset for recoverable signatures with an EIP-155 chain id
+const V_OFFSET_EIP155: u64 = 35;
+
+/// the secp256k1 field prime, big endian
+pub(crate) const FIELD_PRIME: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0xff, 0xff, 0xfc, 0x2f,
+];
+
+/// the secp256k1 group order, big endian
+pub(crate) const CURVE_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// The signature schemes carried by the Es256KMsig codec. Multisigs without
+/// a scheme attribute are ECDSA signatures.
+#[repr(u8)]
+#[derive(Clone, Copy, Default, Hash, Ord, PartialOrd, PartialEq, Eq)]
+pub enum SchemeTypeId {
+    /// ECDSA with a raw r||s signature
+    #[default]
+    Ecdsa,
+    /// BIP-340 Schnorr with an x-only R||s signature
+    Schnorr,
+    /// FROST(secp256k1, SHA-256) Schnorr with a compressed R||z signature
+    /// (RFC 9591)
+    Frost,
+    /// FROST(secp256k1, SHA-256) signature share
+    FrostShare,
+    /// FROST(secp256k1, SHA-256) signature share for a BIP-340 signature
+    FrostTaprootShare,
+    /// MuSig2 partial signatures being aggregated into a BIP-340 signature
+    Musig2,
+    /// MuSig2 partial signature (BIP-327)
+    Musig2Partial,
+    /// presignature-based threshold ECDSA signature share
+    EcdsaShare,
+    /// ECDSA with an r||s signature and a recovery id (Ethereum)
+    EcdsaRecoverable,
+}
+
+impl SchemeTypeId {
+    /// Get the code for the scheme
+    pub fn code(&self) -> u8 {
+        (*self).into()
+    }
+
+    /// Convert the scheme to &str
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Ecdsa => "ecdsa",
+            Self::Schnorr => "bip340-schnorr",
+            Self::Frost => "frost-secp256k1",
+            Self::FrostShare => "frost-secp256k1-share",
+            Self::FrostTaprootShare => "frost-secp256k1-tr-share",
+            Self::Musig2 => "musig2",
+            Self::Musig2Partial => "musig2-partial",
+            Self::EcdsaShare => "ecdsa-share",
+            Self::EcdsaRecoverable => "ecdsa-recoverable",
+        }
+    }
+}
+
+impl From<SchemeTypeId> for u8 {
+    fn from(val: SchemeTypeId) -> Self {
+        val as u8
+    }
+}
+
+impl TryFrom<u8> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            0 => Ok(Self::Ecdsa),
+            1 => Ok(Self::Schnorr),
+            2 => Ok(Self::Frost),
+            3 => Ok(Self::FrostShare),
+            4 => Ok(Self::FrostTaprootShare),
+            5 => Ok(Self::Musig2),
+            6 => Ok(Self::Musig2Partial),
+            7 => Ok(Self::EcdsaShare),
+            8 => Ok(Self::EcdsaRecoverable),
+            _ => Err(AttributesError::InvalidScheme(c).into()),
+        }
+    }
+}
+
+impl From<SchemeTypeId> for Vec<u8> {
+    fn from(val: SchemeTypeId) -> Self {
+        val.code().encode_into()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (id, _) = Self::try_decode_from(bytes)?;
+        Ok(id)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (code, ptr) = u8::try_decode_from(bytes)?;
+        Ok((Self::try_from(code)?, ptr))
+    }
+}
+
+impl TryFrom<&str> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "ecdsa" => Ok(Self::Ecdsa),
+            "bip340-schnorr" => Ok(Self::Schnorr),
+            "frost-secp256k1" => Ok(Self::Frost),
+            "frost-secp256k1-share" => Ok(Self::FrostShare),
+            "frost-secp256k1-tr-share" => Ok(Self::FrostTaprootShare),
+            "musig2" => Ok(Self::Musig2),
+            "musig2-partial" => Ok(Self::Musig2Partial),
+            "ecdsa-share" => Ok(Self::EcdsaShare),
+            "ecdsa-recoverable" => Ok(Self::EcdsaRecoverable),
+            _ => Err(AttributesError::InvalidSchemeName(s.to_string()).into()),
+        }
+    }
+}
+
+impl TryFrom<&Multisig> for SchemeTypeId {
+    type Error = Error;
+
+    /// get the scheme from the Multisig, defaulting to ECDSA when the scheme
+    /// attribute is absent
+    fn try_from(ms: &Multisig) -> Result<Self, Self::Error> {
+        match ms.attributes.get(&AttrId::Scheme) {
+            Some(v) => Self::try_from(Varuint::<u8>::try_from(v.as_slice())?.to_inner()),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+impl fmt::Display for SchemeTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// split a 65-byte r||s||v signature into the r||s signature, the recovery id
+/// and the EIP-155 chain id, if any. v may be the raw recovery id, the recovery
+/// id plus 27 or the EIP-155 value of the recovery parity plus 35 plus twice
+/// the chain id.
+pub(crate) fn from_rsv(bytes: &[u8]) -> Result<(Vec<u8>, u8, Option<u64>), Error> {
+    if bytes.len() != RECOVERABLE_SIGNATURE_SIZE {
+        return Err(AttributesError::InvalidSignatureLength(bytes.len()).into());
+    }
+    let (sig, v) = bytes.split_at(SIGNATURE_SIZE);
+    let v = v[0] as u64;
+    let (recovery_id, chain_id) = match v {
+        0..=3 => (v, None),
+        27..=30 => (v - V_OFFSET, None),
+        V_OFFSET_EIP155.. => ((v - V_OFFSET_EIP155) % 2, Some((v - V_OFFSET_EIP155) / 2)),
+        _ => return Err(AttributesError::InvalidRecoveryId(v).into()),
+    };
+    Ok((sig.to_vec(), recovery_id as u8, chain_id))
+}
 
 pub(crate) struct View<'a> {
     ms: &'a Multisig,
@@ -32,9 +223,10 @@ impl AttrView for View<'_> {
         let encoding = Codec::try_from(v.as_slice())?;
         Ok(encoding)
     }
-    /// Es256K only has one scheme so this is meaningless
+    /// Es256K signatures are ECDSA, recoverable ECDSA, BIP-340 Schnorr or
+    /// FROST Schnorr, ECDSA if the scheme attribute is absent
     fn scheme(&self) -> Result<u8, Error> {
-        Ok(0)
+        Ok(SchemeTypeId::try_from(self.ms)?.code())
     }
 }
 
@@ -47,6 +239,32 @@ impl DataView for View<'_> {
             .attributes
             .get(&AttrId::SigData)
             .ok_or(AttributesError::MissingSignature)?;
+        let scheme = SchemeTypeId::try_from(self.ms)?;
+        if scheme == SchemeTypeId::Frost && sig.len() != FROST_SIGNATURE_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        if scheme == SchemeTypeId::EcdsaRecoverable {
+            // recoverable signatures are a plain r||s signature that must
+            // come with its recovery id
+            if sig.len() != SIGNATURE_SIZE {
+                return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+            }
+            self.recovery_id()?;
+        }
+        if scheme == SchemeTypeId::Schnorr {
+            // BIP-340 signatures are the x coordinate of R followed by s and
+            // verification fails unless R.x < p and s < n
+            if sig.len() != SIGNATURE_SIZE {
+                return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+            }
+            let (rx, s) = sig.split_at(32);
+            if rx >= FIELD_PRIME.as_slice() || s >= CURVE_ORDER.as_slice() {
+                return Err(AttributesError::InvalidSignature(
+                    "BIP-340 signature component out of range".to_string(),
+                )
+                .into());
+            }
+        }
         Ok(sig.clone())
     }
 }
@@ -57,13 +275,95 @@ impl ConvView for View<'_> {
         // get the signature data
         let dv = self.ms.data_view()?;
         let sig_bytes = dv.sig_bytes()?;
+        let (name, sig_bytes) = match SchemeTypeId::try_from(self.ms)? {
+            SchemeTypeId::Ecdsa => (ALGORITHM_NAME, sig_bytes),
+            SchemeTypeId::Schnorr => (ALGORITHM_NAME_SCHNORR, sig_bytes),
+            SchemeTypeId::Frost => (ALGORITHM_NAME_FROST, sig_bytes),
+            // recoverable signatures are exported in the r||s||v form
+            SchemeTypeId::EcdsaRecoverable => (ALGORITHM_NAME_RECOVERABLE, self.to_rsv()?),
+            scheme => return Err(Error::UnsupportedAlgorithm(scheme.to_string())),
+        };
         Ok(ssh_key::Signature::new(
             ssh_key::Algorithm::Other(
-                ssh_key::AlgorithmName::new(ALGORITHM_NAME)
-                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+                ssh_key::AlgorithmName::new(name).map_err(|e| ConversionsError::Ssh(e.into()))?,
             ),
             sig_bytes,
         )
         .map_err(|e| ConversionsError::Ssh(e.into()))?)
     }
 }
+
+impl RecoveryView for View<'_> {
+    /// for recoverable Es256K Multisigs, the recovery id is stored using the
+    /// AttrId::RecoveryId attribute id.
+    fn recovery_id(&self) -> Result<u8, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::RecoveryId)
+            .ok_or(AttributesError::MissingRecoveryId)?;
+        let recovery_id = Varuint::<u8>::try_from(v.as_slice())?.to_inner();
+        if RecoveryId::from_byte(recovery_id).is_none() {
+            return Err(AttributesError::InvalidRecoveryId(recovery_id as u64).into());
+        }
+        Ok(recovery_id)
+    }
+    /// for recoverable Es256K Multisigs, the EIP-155 chain id is stored using
+    /// the AttrId::ChainId attribute id.
+    fn chain_id(&self) -> Result<u64, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::ChainId)
+            .ok_or(AttributesError::MissingChainId)?;
+        Ok(Varuint::<u64>::try_from(v.as_slice())?.to_inner())
+    }
+    /// v is the recovery id plus 27 or, when there is a chain id, the EIP-155
+    /// value of the recovery parity plus 35 plus twice the chain id
+    fn to_rsv(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        if sig.len() != SIGNATURE_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        let recovery_id = self.recovery_id()? as u64;
+        let v = if self.ms.attributes.contains_key(&AttrId::ChainId) {
+            // EIP-155 only encodes the parity of R.y
+            if recovery_id > 1 {
+                return Err(AttributesError::InvalidRecoveryId(recovery_id).into());
+            }
+            self.chain_id()?
+                .checked_mul(2)
+                .and_then(|c| c.checked_add(V_OFFSET_EIP155 + recovery_id))
+                .ok_or(AttributesError::InvalidRecoveryId(recovery_id))?
+        } else {
+            recovery_id + V_OFFSET
+        };
+        // v must fit in the single trailing byte
+        let v = u8::try_from(v).map_err(|_| AttributesError::InvalidRecoveryId(v))?;
+        let mut rsv = sig.clone();
+        rsv.push(v);
+        Ok(rsv)
+    }
+    /// recover the signer's public key from the 32-byte message digest
+    fn recover_public_key(&self, digest: &[u8]) -> Result<Vec<u8>, Error> {
+        if digest.len() != 32 {
+            return Err(AttributesError::RecoveryFailed(format!(
+                "invalid digest length {}",
+                digest.len()
+            ))
+            .into());
+        }
+        let dv = self.ms.data_view()?;
+        let sig = Signature::from_slice(&dv.sig_bytes()?)
+            .map_err(|e| AttributesError::InvalidSignature(e.to_string()))?;
+        let recovery_id =
+            RecoveryId::from_byte(self.recovery_id()?).ok_or(AttributesError::MissingRecoveryId)?;
+        let vk = VerifyingKey::recover_from_prehash(digest, &sig, recovery_id)
+            .map_err(|e| AttributesError::RecoveryFailed(e.to_string()))?;
+        Ok(vk.to_encoded_point(true).as_bytes().to_vec())
+    }
+}
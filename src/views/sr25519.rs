@@ -0,0 +1,115 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    views::eddsa::SchemeTypeId,
+    AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
+};
+use multicodec::Codec;
+
+pub use crate::views::eddsa::SigContext;
+
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME: &str = "sr25519@multisig";
+
+/// the size of an sr25519 signature in bytes
+pub const SIGNATURE_SIZE: usize = 64;
+
+/// schnorrkel sets the high bit of the last signature byte to tell sr25519
+/// signatures apart from Ed25519 signatures
+const SCHNORRKEL_MARKER: u8 = 0x80;
+
+/// check if a Multisig holds an sr25519 signature. sr25519 signatures share
+/// the EddsaMsig codec but are not EdDSA, so the Ed25519 threshold and
+/// aggregate views must never accept them.
+pub(crate) fn is_sr25519(ms: &Multisig) -> bool {
+    ms.codec == Codec::EddsaMsig && matches!(SchemeTypeId::try_from(ms), Ok(SchemeTypeId::Sr25519))
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for sr25519 Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// sr25519 signatures are identified by their EdDSA scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(SchemeTypeId::try_from(self.ms)?.code())
+    }
+    /// sr25519 signatures are always bound to a signing context label (e.g.
+    /// "substrate") which is stored using the AttrId::Context attribute id.
+    /// verification needs the same label.
+    fn context(&self) -> Result<Vec<u8>, Error> {
+        let context = self
+            .ms
+            .attributes
+            .get(&AttrId::Context)
+            .ok_or(AttributesError::MissingContext)?;
+        if context.is_empty() {
+            return Err(AttributesError::InvalidContextLength(0).into());
+        }
+        Ok(context.clone())
+    }
+}
+
+impl DataView for View<'_> {
+    /// For sr25519 Multisig values, the sig data is stored using the
+    /// AttrId::SigData attribute id.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        if sig.len() != SIGNATURE_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        if sig[SIGNATURE_SIZE - 1] & SCHNORRKEL_MARKER == 0 {
+            return Err(AttributesError::InvalidSignature(
+                "missing schnorrkel signature marker".to_string(),
+            )
+            .into());
+        }
+        // the signature is useless without its signing context label
+        self.context()?;
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // create the signing context and sig tuple
+        let sig_data: Vec<u8> = SigContext(self.context()?, sig_bytes).into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(ALGORITHM_NAME)
+                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
@@ -0,0 +1,724 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError, SharesError},
+    views::{p256, secp256k1},
+    AttrId, AttrView, Builder, ConvView, DataView, Error, Multisig, ThresholdAttrView,
+    ThresholdView, Views,
+};
+use elliptic_curve::{
+    consts::U32,
+    group::{Curve as _, Group},
+    ops::Reduce,
+    point::AffineCoordinates,
+    scalar::IsHigh,
+    sec1::{EncodedPoint, FromEncodedPoint, ModulusSize},
+    Curve as EllipticCurve, CurveArithmetic, Field, FieldBytes, FieldBytesSize, PrimeField,
+};
+use multicodec::Codec;
+use multitrait::TryDecodeFrom;
+use multiutil::{Varbytes, Varuint};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// the name used to identify secp256k1 threshold ECDSA signature shares in
+/// non-Multikey formats
+pub const ALGORITHM_NAME_SECP256K1_SHARE: &str = "secp256k1-ecdsa-share@multisig";
+/// the name used to identify P-256 threshold ECDSA signature shares in
+/// non-Multikey formats
+pub const ALGORITHM_NAME_P256_SHARE: &str = "nistp256-ecdsa-share@multisig";
+
+/// the size of a threshold ECDSA signature share in bytes
+pub const SHARE_SIZE: usize = 32;
+/// the largest number of signers, signers are identified by a non-zero u8
+pub const MAX_SIGNERS: usize = u8::MAX as usize;
+
+/// The curves with threshold ECDSA support
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Curve {
+    /// secp256k1 ECDSA producing Es256K signatures
+    Secp256k1,
+    /// NIST P-256 ECDSA producing Es256 signatures
+    P256,
+}
+
+impl Curve {
+    /// the scheme used by signature shares for the curve
+    fn share_scheme(&self) -> u8 {
+        match self {
+            Self::Secp256k1 => secp256k1::SchemeTypeId::EcdsaShare.code(),
+            Self::P256 => p256::SchemeTypeId::EcdsaShare.code(),
+        }
+    }
+
+    /// the name used to identify signature shares in non-Multikey formats
+    fn algorithm_name(&self) -> &'static str {
+        match self {
+            Self::Secp256k1 => ALGORITHM_NAME_SECP256K1_SHARE,
+            Self::P256 => ALGORITHM_NAME_P256_SHARE,
+        }
+    }
+
+    /// combine the signature shares from every signer in the presignature
+    /// into a low-S r||s signature
+    fn combine(
+        &self,
+        presig: &Presignature,
+        shares: &BTreeMap<u8, SigShare>,
+        msg: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Secp256k1 => combine::<k256::Secp256k1>(presig, shares, msg),
+            Self::P256 => combine::<::p256::NistP256>(presig, shares, msg),
+        }
+    }
+}
+
+/// tuple of a signer's public presignature data
+#[derive(Clone, Default, PartialEq)]
+pub struct PresignatureShare(
+    /// signer identifier
+    pub u8,
+    /// the signer's nonce share times R
+    pub Vec<u8>,
+    /// the signer's masked key share times R
+    pub Vec<u8>,
+);
+
+impl From<PresignatureShare> for Vec<u8> {
+    fn from(val: PresignatureShare) -> Self {
+        let mut v = Vec::default();
+        // add in the signer identifier
+        v.append(&mut Varuint(val.0).into());
+        // add in the nonce share point
+        v.append(&mut Varbytes(val.1.clone()).into());
+        // add in the masked key share point
+        v.append(&mut Varbytes(val.2.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for PresignatureShare {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signer identifier
+        let (id, ptr) = Varuint::<u8>::try_decode_from(bytes)?;
+        // try to decode the nonce share point
+        let (nonce, ptr) = Varbytes::try_decode_from(ptr)?;
+        // try to decode the masked key share point
+        let (key, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((Self(id.to_inner(), nonce.to_inner(), key.to_inner()), ptr))
+    }
+}
+
+/// tuple of the verifying key, the presignature point R and the public
+/// presignature data of each signer. the presignature is bound to the set of
+/// signers that generated it and every signature share in a signing session
+/// is computed from the same presignature.
+#[derive(Clone, Default, PartialEq)]
+pub struct Presignature(
+    /// verifying key
+    pub Vec<u8>,
+    /// presignature point R, the inverse of the nonce times the generator
+    pub Vec<u8>,
+    /// public presignature data, sorted by signer identifier
+    pub Vec<PresignatureShare>,
+);
+
+impl From<Presignature> for Vec<u8> {
+    fn from(val: Presignature) -> Self {
+        let mut v = Vec::default();
+        // add in the verifying key
+        v.append(&mut Varbytes(val.0.clone()).into());
+        // add in the presignature point
+        v.append(&mut Varbytes(val.1.clone()).into());
+        // add in the number of presignature shares
+        v.append(&mut Varuint(val.2.len()).into());
+        // add in the presignature shares
+        val.2.iter().for_each(|s| {
+            v.append(&mut s.clone().into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Presignature {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (presig, _) = Self::try_decode_from(bytes)?;
+        Ok(presig)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for Presignature {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the verifying key
+        let (vk, ptr) = Varbytes::try_decode_from(bytes)?;
+        // try to decode the presignature point
+        let (r, ptr) = Varbytes::try_decode_from(ptr)?;
+        // try to decode the number of presignature shares
+        let (num_shares, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        if *num_shares > MAX_SIGNERS {
+            return Err(SharesError::TooManyShares.into());
+        }
+        // try to decode the presignature shares
+        let mut shares: Vec<PresignatureShare> = Vec::with_capacity(*num_shares);
+        let mut p = ptr;
+        for _ in 0..*num_shares {
+            let (s, ptr) = PresignatureShare::try_decode_from(p)?;
+            // the presignature shares are sorted by unique, non-zero identifiers
+            let last = shares.last().map(|l| l.0).unwrap_or_default();
+            if s.0 <= last {
+                return Err(SharesError::InvalidShareIdentifier(s.0).into());
+            }
+            shares.push(s);
+            p = ptr;
+        }
+        Ok((Self(vk.to_inner(), r.to_inner(), shares), p))
+    }
+}
+
+/// tuple of signature share data with threshold attributes
+#[derive(Clone)]
+pub struct SigShare(
+    /// identifier
+    pub u8,
+    /// threshold
+    pub usize,
+    /// limit
+    pub usize,
+    /// share bytes
+    pub Vec<u8>,
+);
+
+impl From<SigShare> for Vec<u8> {
+    fn from(val: SigShare) -> Self {
+        let mut v = Vec::default();
+        // add in the share identifier
+        v.append(&mut Varuint(val.0).into());
+        // add in the share threshold
+        v.append(&mut Varuint(val.1).into());
+        // add in the share limit
+        v.append(&mut Varuint(val.2).into());
+        // add in the share data
+        v.append(&mut Varbytes(val.3.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigShare {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the identifier
+        let (id, ptr) = Varuint::<u8>::try_decode_from(bytes)?;
+        // try to decode the threshold
+        let (threshold, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        // try to decode the limit
+        let (limit, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        // try to decode the share data
+        let (share_data, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((
+            Self(
+                id.to_inner(),
+                threshold.to_inner(),
+                limit.to_inner(),
+                share_data.to_inner(),
+            ),
+            ptr,
+        ))
+    }
+}
+
+/// tuple of a signature share and the presignature it was computed from
+#[derive(Clone)]
+pub struct SigSharePackage(
+    /// signature share
+    pub SigShare,
+    /// presignature
+    pub Presignature,
+);
+
+impl From<SigSharePackage> for Vec<u8> {
+    fn from(val: SigSharePackage) -> Self {
+        let mut v = Vec::default();
+        // add in the signature share
+        v.append(&mut val.0.into());
+        // add in the presignature
+        v.append(&mut val.1.into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigSharePackage {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (share, _) = Self::try_decode_from(bytes)?;
+        Ok(share)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigSharePackage {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signature share
+        let (share, ptr) = SigShare::try_decode_from(bytes)?;
+        // try to decode the presignature
+        let (presig, ptr) = Presignature::try_decode_from(ptr)?;
+        Ok((Self(share, presig), ptr))
+    }
+}
+
+/// the presignature and the signature shares accumulated so far
+#[derive(Clone, Default)]
+pub(crate) struct ThresholdData(pub(crate) Presignature, pub(crate) BTreeMap<u8, SigShare>);
+
+impl From<ThresholdData> for Vec<u8> {
+    fn from(val: ThresholdData) -> Self {
+        let mut v = Vec::default();
+        // add in the presignature
+        v.append(&mut val.0.into());
+        // add in the number of sig shares
+        v.append(&mut Varuint(val.1.len()).into());
+        // add in the sig shares
+        val.1.iter().for_each(|(_, share)| {
+            v.append(&mut share.clone().into());
+        });
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ThresholdData {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (tdata, _) = Self::try_decode_from(bytes)?;
+        Ok(tdata)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for ThresholdData {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the presignature
+        let (presig, ptr) = Presignature::try_decode_from(bytes)?;
+        // try to decode the number of shares
+        let (num_shares, ptr) = Varuint::<usize>::try_decode_from(ptr)?;
+        if *num_shares > MAX_SIGNERS {
+            return Err(SharesError::TooManyShares.into());
+        }
+        // try to decode the shares
+        let mut shares = BTreeMap::new();
+        let mut p = ptr;
+        for _ in 0..*num_shares {
+            let (share, ptr) = SigShare::try_decode_from(p)?;
+            shares.insert(share.0, share);
+            p = ptr;
+        }
+        Ok((Self(presig, shares), p))
+    }
+}
+
+/// deserialize a compressed SEC1 encoded point
+fn point<C>(bytes: &[u8]) -> Result<C::ProjectivePoint, Error>
+where
+    C: CurveArithmetic,
+    C::AffinePoint: FromEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let err = || SharesError::ShareCombineFailed("invalid curve point".to_string());
+    let encoded = EncodedPoint::<C>::from_bytes(bytes).map_err(|_| err())?;
+    if !encoded.is_compressed() {
+        return Err(err().into());
+    }
+    Option::<C::AffinePoint>::from(C::AffinePoint::from_encoded_point(&encoded))
+        .map(C::ProjectivePoint::from)
+        .filter(|p| !bool::from(p.is_identity()))
+        .ok_or_else(|| err().into())
+}
+
+/// deserialize a canonically encoded scalar
+fn scalar<C>(bytes: &[u8]) -> Result<C::Scalar, Error>
+where
+    C: CurveArithmetic + EllipticCurve<FieldBytesSize = U32>,
+{
+    let err = || SharesError::ShareCombineFailed("invalid scalar".to_string());
+    let b: [u8; 32] = bytes.try_into().map_err(|_| err())?;
+    Option::<C::Scalar>::from(C::Scalar::from_repr(FieldBytes::<C>::from(b)))
+        .ok_or_else(|| err().into())
+}
+
+/// check every signature share against the presignature and sum them into a
+/// low-S ECDSA signature over the SHA-256 digest of the message. a share that
+/// does not match its signer's presignature data identifies the signer that
+/// caused the abort.
+fn combine<C>(
+    presig: &Presignature,
+    shares: &BTreeMap<u8, SigShare>,
+    msg: &[u8],
+) -> Result<Vec<u8>, Error>
+where
+    C: CurveArithmetic + EllipticCurve<FieldBytesSize = U32>,
+    C::AffinePoint: FromEncodedPoint<C>,
+{
+    let vk = point::<C>(&presig.0)?;
+    let r_point = point::<C>(&presig.1)?;
+    let r = <C::Scalar as Reduce<C::Uint>>::reduce_bytes(&r_point.to_affine().x());
+    let m = <C::Scalar as Reduce<C::Uint>>::reduce_bytes(&Sha256::digest(msg));
+
+    // the nonce shares must sum to the generator and the masked key shares
+    // to the verifying key, otherwise no signer can be blamed
+    let mut nonce_sum = C::ProjectivePoint::identity();
+    let mut key_sum = C::ProjectivePoint::identity();
+    let mut s = C::Scalar::ZERO;
+    for p in &presig.2 {
+        let nonce = point::<C>(&p.1)?;
+        let key = point::<C>(&p.2)?;
+        nonce_sum += nonce;
+        key_sum += key;
+        let share = shares.get(&p.0).ok_or_else(|| {
+            SharesError::ShareCombineFailed(format!("missing signature share {}", p.0))
+        })?;
+        let sigma = scalar::<C>(&share.3).map_err(|_| SharesError::IdentifiableAbort(p.0))?;
+        if r_point * sigma != nonce * m + key * r {
+            return Err(SharesError::IdentifiableAbort(p.0).into());
+        }
+        s += sigma;
+    }
+    if nonce_sum != C::ProjectivePoint::generator() || key_sum != vk {
+        return Err(
+            SharesError::ShareCombineFailed("inconsistent presignature".to_string()).into(),
+        );
+    }
+    if bool::from(r.is_zero()) || bool::from(s.is_zero()) {
+        return Err(SharesError::ShareCombineFailed("invalid signature".to_string()).into());
+    }
+
+    // normalize to low-S so the signature is accepted by strict verifiers
+    if bool::from(s.is_high()) {
+        s = -s;
+    }
+    let mut sig = r.to_repr().to_vec();
+    sig.extend_from_slice(&s.to_repr());
+    Ok(sig)
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl View<'_> {
+    /// get the curve and whether this Multisig is a signature share.
+    /// Multisigs accumulating shares are plain ECDSA Multisigs.
+    fn curve(&self) -> Result<(Curve, bool), Error> {
+        match self.ms.codec {
+            Codec::Es256KMsig => match secp256k1::SchemeTypeId::try_from(self.ms)? {
+                secp256k1::SchemeTypeId::Ecdsa => Ok((Curve::Secp256k1, false)),
+                secp256k1::SchemeTypeId::EcdsaShare => Ok((Curve::Secp256k1, true)),
+                scheme => Err(Error::UnsupportedAlgorithm(scheme.to_string())),
+            },
+            Codec::Es256Msig => match p256::SchemeTypeId::try_from(self.ms)? {
+                p256::SchemeTypeId::Ecdsa => Ok((Curve::P256, false)),
+                p256::SchemeTypeId::EcdsaShare => Ok((Curve::P256, true)),
+            },
+            _ => Err(Error::UnsupportedAlgorithm(self.ms.codec.to_string())),
+        }
+    }
+
+    /// true if this Multisig is a threshold ECDSA signature share
+    fn is_share(&self) -> Result<bool, Error> {
+        Ok(self.curve()?.1)
+    }
+
+    /// check that this Multisig accumulates threshold ECDSA signature shares
+    fn check_accumulator(&self) -> Result<Curve, Error> {
+        match self.curve()? {
+            (_, true) => Err(SharesError::IsASignatureShare.into()),
+            (curve, false) => Ok(curve),
+        }
+    }
+
+    /// get the threshold data accumulated so far
+    fn accumulated(&self) -> ThresholdData {
+        match self.ms.attributes.get(&AttrId::ThresholdData) {
+            Some(b) => ThresholdData::try_from(b.as_slice()).unwrap_or_default(),
+            None => ThresholdData::default(),
+        }
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for threshold ECDSA Multisigs, the payload encoding is stored using
+    /// the AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// threshold ECDSA signature shares are identified by their scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        match self.ms.codec {
+            Codec::Es256Msig => Ok(p256::SchemeTypeId::try_from(self.ms)?.code()),
+            _ => Ok(secp256k1::SchemeTypeId::try_from(self.ms)?.code()),
+        }
+    }
+}
+
+impl DataView for View<'_> {
+    /// For threshold ECDSA signature shares, the signature share is stored
+    /// using the AttrId::SigData attribute id.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        if self.is_share()? && sig.len() != SHARE_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        let (curve, is_share) = self.curve()?;
+        if !is_share {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // get the threshold attributes
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold()?;
+        let limit = av.limit()?;
+        let identifier = av.identifier()?;
+        let presig = Presignature::try_from(av.threshold_data()?)?;
+
+        // create the sig share tuple
+        let sig_data: Vec<u8> =
+            SigSharePackage(SigShare(identifier, threshold, limit, sig_bytes), presig).into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(curve.algorithm_name())
+                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
+
+impl ThresholdAttrView for View<'_> {
+    /// get the threshold value for this multisig
+    fn threshold(&self) -> Result<usize, Error> {
+        let threshold = self
+            .ms
+            .attributes
+            .get(&AttrId::Threshold)
+            .ok_or(AttributesError::MissingThreshold)?;
+        Ok(Varuint::<usize>::try_from(threshold.as_slice())?.to_inner())
+    }
+    /// get the limit value for this multisig
+    fn limit(&self) -> Result<usize, Error> {
+        let limit = self
+            .ms
+            .attributes
+            .get(&AttrId::Limit)
+            .ok_or(AttributesError::MissingLimit)?;
+        Ok(Varuint::<usize>::try_from(limit.as_slice())?.to_inner())
+    }
+    /// get the share identifier
+    fn identifier(&self) -> Result<u8, Error> {
+        if !self.is_share()? {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+        let identifier = self
+            .ms
+            .attributes
+            .get(&AttrId::ShareIdentifier)
+            .ok_or(AttributesError::MissingIdentifier)?;
+        Ok(Varuint::<u8>::try_from(identifier.as_slice())?.to_inner())
+    }
+    /// get the threshold data. for signature shares this is the
+    /// presignature, for the accumulating Multisig it is the presignature and
+    /// the shares added so far.
+    fn threshold_data(&self) -> Result<&[u8], Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::ThresholdData)
+            .ok_or(AttributesError::MissingThresholdData)?;
+        Ok(v.as_slice())
+    }
+}
+
+/// trait for accumulating shares to combine a threshold ECDSA signature
+impl ThresholdView for View<'_> {
+    /// get the signature shares
+    fn shares(&self) -> Result<Vec<Multisig>, Error> {
+        let curve = self.check_accumulator()?;
+
+        let threshold_data = self.accumulated();
+        let presig: Vec<u8> = threshold_data.0.into();
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            av.payload_encoding().ok()
+        };
+
+        // build multisigs out of each share, preserve the message and the
+        // payload encoding value
+        threshold_data
+            .1
+            .values()
+            .map(|share| {
+                let builder = Builder::new(self.ms.codec)
+                    .with_message_bytes(&self.ms.message.as_slice())
+                    .with_scheme(curve.share_scheme())
+                    .with_identifier(share.0)
+                    .with_threshold(share.1)
+                    .with_limit(share.2)
+                    .with_signature_bytes(&share.3)
+                    .with_threshold_data(&presig);
+                if let Some(encoding) = encoding {
+                    builder.with_payload_encoding(encoding).try_build()
+                } else {
+                    builder.try_build()
+                }
+            })
+            .collect()
+    }
+    /// add a new share and return the Multisig with the share added
+    fn add_share(&self, share: &Multisig) -> Result<Multisig, Error> {
+        self.check_accumulator()?;
+
+        // the share must be a threshold ECDSA signature share for the same
+        // curve
+        if share.codec != self.ms.codec || !View::try_from(share)?.is_share()? {
+            return Err(SharesError::NotASignatureShare.into());
+        }
+
+        let (sdata, presig, encoding) = {
+            // get the share's attributes
+            let av = share.threshold_attr_view()?;
+            let threshold = av.threshold()?;
+            let limit = av.limit()?;
+            let identifier = av.identifier()?;
+            let presig = Presignature::try_from(av.threshold_data()?)?;
+
+            // get the share's signature data
+            let dv = share.data_view()?;
+            let sig_bytes = dv.sig_bytes()?;
+
+            let encoding = {
+                let av = share.attr_view()?;
+                av.payload_encoding().ok()
+            };
+
+            (
+                SigShare(identifier, threshold, limit, sig_bytes),
+                presig,
+                encoding,
+            )
+        };
+
+        // update the threshold data
+        let ThresholdData(mut presignature, mut shares) = self.accumulated();
+        let Presignature(_, _, presig_shares) = &presignature;
+        if presig_shares.is_empty() {
+            // adopt the presignature from the first share added
+            presignature = presig;
+        } else if presignature != presig {
+            return Err(SharesError::SigningPackageMismatch.into());
+        }
+        // only signers in the presignature can contribute shares
+        let Presignature(_, _, presig_shares) = &presignature;
+        if !presig_shares.iter().any(|p| p.0 == sdata.0) {
+            return Err(SharesError::InvalidShareIdentifier(sdata.0).into());
+        }
+        let (threshold, limit) = (sdata.1, sdata.2);
+        shares.insert(sdata.0, sdata);
+        let threshold_data: Vec<u8> = ThresholdData(presignature, shares).into();
+
+        // get the payload encoding
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            // if this multisig doesn't have payload encoding set, set it to
+            // the value from the first share added
+            match av.payload_encoding() {
+                Ok(encoding) => Some(encoding),
+                Err(_) => encoding,
+            }
+        };
+
+        // if this multisig doesn't already have the threshold/limit set then
+        // set it to match the values from the first share added
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold().unwrap_or(threshold);
+        let limit = av.limit().unwrap_or(limit);
+
+        let builder = Builder::new(self.ms.codec)
+            .with_message_bytes(&self.ms.message.as_slice())
+            .with_threshold(threshold)
+            .with_limit(limit)
+            .with_threshold_data(&threshold_data);
+
+        if let Some(encoding) = encoding {
+            builder.with_payload_encoding(encoding).try_build()
+        } else {
+            builder.try_build()
+        }
+    }
+    /// combine the signature shares. ECDSA signatures are computed over the
+    /// message so it must be attached to this Multisig.
+    fn combine(&self) -> Result<Multisig, Error> {
+        let curve = self.check_accumulator()?;
+
+        let ThresholdData(presig, shares) = self.accumulated();
+
+        // check that we have enough shares to combine
+        let av = self.ms.threshold_attr_view()?;
+        let threshold = av.threshold()?;
+        if shares.len() < threshold || presig.2.len() < threshold {
+            return Err(SharesError::NotEnoughShares.into());
+        }
+
+        let sig_bytes = curve.combine(&presig, &shares, &self.ms.message)?;
+        let encoding = {
+            let av = self.ms.attr_view()?;
+            av.payload_encoding().ok()
+        };
+        let builder = Builder::new(self.ms.codec)
+            .with_message_bytes(&self.ms.message.as_slice())
+            .with_signature_bytes(&sig_bytes);
+
+        if let Some(encoding) = encoding {
+            builder.with_payload_encoding(encoding).try_build()
+        } else {
+            builder.try_build()
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    views::eddsa::SchemeTypeId,
+    AttrId, AttrView, ConvView, DataView, Error, Multisig, Views,
+};
+use multicodec::Codec;
+use multitrait::TryDecodeFrom;
+use multiutil::Varbytes;
+
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME: &str = "xeddsa@multisig";
+
+/// the size of an XEdDSA signature in bytes
+pub const SIGNATURE_SIZE: usize = 64;
+
+/// XEdDSA signatures are verified against a Montgomery-form X25519 public key
+/// and not an Ed25519 public key
+pub const VERIFYING_KEY_CODEC: Codec = Codec::X25519Pub;
+
+/// tuple of the verifying key codec and signature data
+#[derive(Clone)]
+pub struct SigKeyType(
+    /// the codec of the public key to verify against
+    pub Codec,
+    /// signature bytes
+    pub Vec<u8>,
+);
+
+impl From<SigKeyType> for Vec<u8> {
+    fn from(val: SigKeyType) -> Self {
+        let mut v = Vec::default();
+        // add in the verifying key codec
+        v.append(&mut val.0.into());
+        // add in the signature bytes
+        v.append(&mut Varbytes(val.1.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigKeyType {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (sig, _) = Self::try_decode_from(bytes)?;
+        Ok(sig)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigKeyType {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the verifying key codec
+        let (key_codec, ptr) = Codec::try_decode_from(bytes)?;
+        if key_codec != VERIFYING_KEY_CODEC {
+            return Err(Error::UnsupportedAlgorithm(key_codec.to_string()));
+        }
+        // try to decode the signature bytes
+        let (sig_data, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((Self(key_codec, sig_data.to_inner()), ptr))
+    }
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        Ok(Self { ms })
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for XEdDSA Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// XEdDSA signatures are identified by their EdDSA scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(SchemeTypeId::try_from(self.ms)?.code())
+    }
+}
+
+impl DataView for View<'_> {
+    /// For XEdDSA Multisig values, the sig data is stored using the
+    /// AttrId::SigData attribute id.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        if sig.len() != SIGNATURE_SIZE {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format. the signature data states that it
+    /// must be verified against an X25519 public key.
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // create the key type and sig tuple
+        let sig_data: Vec<u8> = SigKeyType(VERIFYING_KEY_CODEC, sig_bytes).into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(ALGORITHM_NAME)
+                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
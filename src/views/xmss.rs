@@ -0,0 +1,402 @@
+// SPDX-License-Idnetifier: Apache-2.0
+use crate::{
+    error::{AttributesError, ConversionsError},
+    AttrId, AttrView, ConvView, DataView, Error, Multisig, StatefulView, Views,
+};
+use multicodec::Codec;
+use multitrait::{EncodeInto, TryDecodeFrom};
+use multiutil::{Varbytes, Varuint};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+/// the name used to identify these signatures in non-Multikey formats
+pub const ALGORITHM_NAME: &str = "xmss-lms@multisig";
+
+/// the largest supported tree height, leaf indexes are 64-bit
+pub const MAX_TREE_HEIGHT: u8 = 64;
+
+/// the number of Winternitz chains in a one-time signature with n = 32 and
+/// w = 16
+const WOTS_LEN: usize = 67;
+
+/// The stateful hash-based signature schemes carried by the LamportMsig codec,
+/// the XMSS, XMSS^MT, LMS and HSS ([SP 800-208][1]) families with 256-bit hash
+/// outputs. They are Merkle trees of one-time signatures, so they share the
+/// Lamport codec, but their codes follow a gap after the Lamport scheme codes
+/// so the scheme attribute tells them apart.
+///
+/// [1]: https://csrc.nist.gov/pubs/sp/800/208/final
+#[repr(u8)]
+#[derive(Clone, Copy, Hash, Ord, PartialOrd, PartialEq, Eq)]
+pub enum SchemeTypeId {
+    /// XMSS with SHA-256
+    XmssSha2256 = 15,
+    /// XMSS with SHAKE256
+    XmssShake256,
+    /// XMSS^MT with SHA-256
+    XmssMtSha2256,
+    /// XMSS^MT with SHAKE256
+    XmssMtShake256,
+    /// LMS with SHA-256
+    LmsSha2256,
+    /// LMS with SHAKE256
+    LmsShake256,
+    /// HSS with SHA-256
+    HssSha2256,
+    /// HSS with SHAKE256
+    HssShake256,
+}
+
+impl SchemeTypeId {
+    /// Get the code for the scheme
+    pub fn code(&self) -> u8 {
+        (*self).into()
+    }
+
+    /// Convert the scheme to &str
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::XmssSha2256 => "xmss-sha2-256",
+            Self::XmssShake256 => "xmss-shake-256",
+            Self::XmssMtSha2256 => "xmss-mt-sha2-256",
+            Self::XmssMtShake256 => "xmss-mt-shake-256",
+            Self::LmsSha2256 => "lms-sha2-256",
+            Self::LmsShake256 => "lms-shake-256",
+            Self::HssSha2256 => "hss-sha2-256",
+            Self::HssShake256 => "hss-shake-256",
+        }
+    }
+
+    /// Get the hash function codec used by the scheme
+    pub fn hash(&self) -> Codec {
+        match self {
+            Self::XmssSha2256 | Self::XmssMtSha2256 | Self::LmsSha2256 | Self::HssSha2256 => {
+                Codec::Sha2256
+            }
+            _ => Codec::Shake256,
+        }
+    }
+
+    /// Get the size of the hash digest in bytes
+    pub fn hash_size(&self) -> usize {
+        32
+    }
+}
+
+impl From<SchemeTypeId> for u8 {
+    fn from(val: SchemeTypeId) -> Self {
+        val as u8
+    }
+}
+
+impl TryFrom<u8> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            15 => Ok(Self::XmssSha2256),
+            16 => Ok(Self::XmssShake256),
+            17 => Ok(Self::XmssMtSha2256),
+            18 => Ok(Self::XmssMtShake256),
+            19 => Ok(Self::LmsSha2256),
+            20 => Ok(Self::LmsShake256),
+            21 => Ok(Self::HssSha2256),
+            22 => Ok(Self::HssShake256),
+            _ => Err(AttributesError::InvalidScheme(c).into()),
+        }
+    }
+}
+
+impl From<SchemeTypeId> for Vec<u8> {
+    fn from(val: SchemeTypeId) -> Self {
+        val.code().encode_into()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (id, _) = Self::try_decode_from(bytes)?;
+        Ok(id)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        let (code, ptr) = u8::try_decode_from(bytes)?;
+        Ok((Self::try_from(code)?, ptr))
+    }
+}
+
+impl TryFrom<&str> for SchemeTypeId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "xmss-sha2-256" => Ok(Self::XmssSha2256),
+            "xmss-shake-256" => Ok(Self::XmssShake256),
+            "xmss-mt-sha2-256" => Ok(Self::XmssMtSha2256),
+            "xmss-mt-shake-256" => Ok(Self::XmssMtShake256),
+            "lms-sha2-256" => Ok(Self::LmsSha2256),
+            "lms-shake-256" => Ok(Self::LmsShake256),
+            "hss-sha2-256" => Ok(Self::HssSha2256),
+            "hss-shake-256" => Ok(Self::HssShake256),
+            _ => Err(AttributesError::InvalidSchemeName(s.to_string()).into()),
+        }
+    }
+}
+
+impl TryFrom<&Multisig> for SchemeTypeId {
+    type Error = Error;
+
+    /// get the stateful scheme from the Multisig. stateful signatures always
+    /// record their scheme.
+    fn try_from(ms: &Multisig) -> Result<Self, Self::Error> {
+        let v = ms
+            .attributes
+            .get(&AttrId::Scheme)
+            .ok_or(AttributesError::MissingScheme)?;
+        Self::try_from(Varuint::<u8>::try_from(v.as_slice())?.to_inner())
+    }
+}
+
+impl fmt::Display for SchemeTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// check if a Multisig holds a stateful XMSS or LMS signature rather than a
+/// Lamport signature
+pub(crate) fn is_stateful(ms: &Multisig) -> bool {
+    ms.codec == Codec::LamportMsig && SchemeTypeId::try_from(ms).is_ok()
+}
+
+/// tuple of stateful signature data with the leaf index and tree height
+#[derive(Clone)]
+pub struct SigStateful(
+    /// signature scheme
+    pub SchemeTypeId,
+    /// one-time leaf index
+    pub u64,
+    /// tree height
+    pub u8,
+    /// signature bytes
+    pub Vec<u8>,
+);
+
+impl From<SigStateful> for Vec<u8> {
+    fn from(val: SigStateful) -> Self {
+        let mut v = Vec::default();
+        // add in the signature type id
+        v.append(&mut val.0.into());
+        // add in the leaf index
+        v.append(&mut Varuint(val.1).into());
+        // add in the tree height
+        v.append(&mut Varuint(val.2).into());
+        // add in the signature bytes
+        v.append(&mut Varbytes(val.3.clone()).into());
+        v
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SigStateful {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        let (sig, _) = Self::try_decode_from(bytes)?;
+        Ok(sig)
+    }
+}
+
+impl<'a> TryDecodeFrom<'a> for SigStateful {
+    type Error = Error;
+
+    fn try_decode_from(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), Self::Error> {
+        // try to decode the signature type
+        let (sig_type, ptr) = SchemeTypeId::try_decode_from(bytes)?;
+        // try to decode the leaf index
+        let (leaf_index, ptr) = Varuint::<u64>::try_decode_from(ptr)?;
+        // try to decode the tree height
+        let (tree_height, ptr) = Varuint::<u8>::try_decode_from(ptr)?;
+        // try to decode the signature bytes
+        let (sig_data, ptr) = Varbytes::try_decode_from(ptr)?;
+        Ok((
+            Self(
+                sig_type,
+                leaf_index.to_inner(),
+                tree_height.to_inner(),
+                sig_data.to_inner(),
+            ),
+            ptr,
+        ))
+    }
+}
+
+/// Find the leaf indexes used to make more than one distinct signature. The
+/// signatures must all be made with the same stateful hash-based key. Signing
+/// twice with the same one-time leaf breaks the security of XMSS and LMS, so
+/// any index returned means the key must no longer be trusted.
+pub fn find_leaf_index_reuse(sigs: &[Multisig]) -> Result<Vec<u64>, Error> {
+    let mut seen: BTreeMap<u64, Vec<u8>> = BTreeMap::new();
+    let mut reused = BTreeSet::new();
+    for ms in sigs {
+        let leaf_index = ms.stateful_view()?.leaf_index()?;
+        let sig = ms.data_view()?.sig_bytes()?;
+        match seen.get(&leaf_index) {
+            Some(first) if *first != sig => {
+                reused.insert(leaf_index);
+            }
+            Some(_) => {}
+            None => {
+                seen.insert(leaf_index, sig);
+            }
+        }
+    }
+    Ok(reused.into_iter().collect())
+}
+
+pub(crate) struct View<'a> {
+    ms: &'a Multisig,
+    scheme: SchemeTypeId,
+}
+
+impl<'a> TryFrom<&'a Multisig> for View<'a> {
+    type Error = Error;
+
+    fn try_from(ms: &'a Multisig) -> Result<Self, Self::Error> {
+        let scheme = SchemeTypeId::try_from(ms)?;
+        Ok(Self { ms, scheme })
+    }
+}
+
+impl View<'_> {
+    /// check a stateful signature against its leaf index and tree height.
+    /// XMSS, XMSS^MT and LMS signatures start with the big-endian leaf index.
+    /// HSS signatures start with the number of signed public keys instead.
+    fn check_stateful(&self, sig: &[u8]) -> Result<(), Error> {
+        let leaf_index = self.leaf_index()?;
+        let tree_height = self.tree_height()?;
+        if tree_height < MAX_TREE_HEIGHT && leaf_index >> tree_height != 0 {
+            return Err(AttributesError::InvalidLeafIndex(leaf_index).into());
+        }
+        let index_size = match self.scheme {
+            SchemeTypeId::XmssMtSha2256 | SchemeTypeId::XmssMtShake256 => {
+                usize::from(tree_height).div_ceil(8)
+            }
+            SchemeTypeId::HssSha2256 | SchemeTypeId::HssShake256 => 0,
+            _ => 4,
+        };
+        if sig.len() <= index_size.max(4) {
+            return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+        }
+        // an XMSS signature is the index, the randomizer, the one-time
+        // signature and the authentication path
+        if matches!(
+            self.scheme,
+            SchemeTypeId::XmssSha2256 | SchemeTypeId::XmssShake256
+        ) {
+            let n = self.scheme.hash_size();
+            if sig.len() != 4 + n + (WOTS_LEN + usize::from(tree_height)) * n {
+                return Err(AttributesError::InvalidSignatureLength(sig.len()).into());
+            }
+        }
+        let index = sig[..index_size]
+            .iter()
+            .fold(0u64, |i, b| (i << 8) | u64::from(*b));
+        if index_size != 0 && index != leaf_index {
+            return Err(AttributesError::InvalidLeafIndex(leaf_index).into());
+        }
+        Ok(())
+    }
+}
+
+impl AttrView for View<'_> {
+    /// for stateful Multisigs, the payload encoding is stored using the
+    /// AttrId::PayloadEncoding attribute id.
+    fn payload_encoding(&self) -> Result<Codec, Error> {
+        let v = self
+            .ms
+            .attributes
+            .get(&AttrId::PayloadEncoding)
+            .ok_or(AttributesError::MissingPayloadEncoding)?;
+        let encoding = Codec::try_from(v.as_slice())?;
+        Ok(encoding)
+    }
+    /// stateful signatures record the parameter set in the scheme
+    fn scheme(&self) -> Result<u8, Error> {
+        Ok(self.scheme.code())
+    }
+}
+
+impl DataView for View<'_> {
+    /// For stateful Multisig values, the sig data is stored using the
+    /// AttrId::SigData attribute id. the signature is checked against the
+    /// leaf index and tree height.
+    fn sig_bytes(&self) -> Result<Vec<u8>, Error> {
+        let sig = self
+            .ms
+            .attributes
+            .get(&AttrId::SigData)
+            .ok_or(AttributesError::MissingSignature)?;
+        self.check_stateful(sig)?;
+        Ok(sig.clone())
+    }
+}
+
+impl ConvView for View<'_> {
+    /// convert to SSH signature format
+    fn to_ssh_signature(&self) -> Result<ssh_key::Signature, Error> {
+        // get the signature data
+        let dv = self.ms.data_view()?;
+        let sig_bytes = dv.sig_bytes()?;
+
+        // create the stateful sig tuple
+        let sig_data: Vec<u8> = SigStateful(
+            self.scheme,
+            self.leaf_index()?,
+            self.tree_height()?,
+            sig_bytes,
+        )
+        .into();
+
+        Ok(ssh_key::Signature::new(
+            ssh_key::Algorithm::Other(
+                ssh_key::AlgorithmName::new(ALGORITHM_NAME)
+                    .map_err(|e| ConversionsError::Ssh(e.into()))?,
+            ),
+            sig_data,
+        )
+        .map_err(|e| ConversionsError::Ssh(e.into()))?)
+    }
+}
+
+impl StatefulView for View<'_> {
+    /// get the one-time leaf index the signature was made with
+    fn leaf_index(&self) -> Result<u64, Error> {
+        let leaf_index = self
+            .ms
+            .attributes
+            .get(&AttrId::LeafIndex)
+            .ok_or(AttributesError::MissingLeafIndex)?;
+        Ok(Varuint::<u64>::try_from(leaf_index.as_slice())?.to_inner())
+    }
+    /// get the total height of the tree, or hypertree, of one-time leaves
+    fn tree_height(&self) -> Result<u8, Error> {
+        let tree_height = self
+            .ms
+            .attributes
+            .get(&AttrId::TreeHeight)
+            .ok_or(AttributesError::MissingTreeHeight)?;
+        let tree_height = Varuint::<u8>::try_from(tree_height.as_slice())?.to_inner();
+        if tree_height == 0 || tree_height > MAX_TREE_HEIGHT {
+            return Err(AttributesError::InvalidTreeHeight(tree_height).into());
+        }
+        Ok(tree_height)
+    }
+}